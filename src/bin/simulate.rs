@@ -0,0 +1,70 @@
+use std::{env, process};
+
+use rust_poker::game::{
+    betting::BettingStructure,
+    player::{CallingStation, RandomPlayer, RuleBasedPlayer},
+    simulate::{Entrant, Simulation},
+};
+
+const USAGE: &str = "Usage: simulate [--hands N | --tournaments N] [--seed N] \
+[--structure no-limit|pot-limit|fixed-limit]";
+
+// Plays the reference bots against each other and prints the win rate of each of them. Runs
+// 10000 hands of a cash game unless a number of sit-and-go tournaments is given.
+fn main() {
+    let mut simulation = Simulation::default();
+    let mut hands = 10_000;
+    let mut tournaments = None;
+
+    let args: Vec<String> = env::args().skip(1).collect();
+    for pair in args.chunks(2) {
+        let [flag, value] = pair else {
+            exit_with_usage(&format!("Missing value for {}", pair[0]));
+        };
+
+        let number = || {
+            value
+                .parse::<u64>()
+                .unwrap_or_else(|_| exit_with_usage(&format!("Invalid number: {}", value)))
+        };
+
+        match flag.as_str() {
+            "--hands" => hands = number() as usize,
+            "--tournaments" => tournaments = Some(number() as usize),
+            "--seed" => simulation.seed = number(),
+            "--structure" => {
+                simulation.structure = match value.as_str() {
+                    "no-limit" => BettingStructure::NoLimit,
+                    "pot-limit" => BettingStructure::PotLimit,
+                    "fixed-limit" => BettingStructure::FixedLimit,
+                    _ => exit_with_usage(&format!("Invalid betting structure: {}", value)),
+                }
+            }
+            _ => exit_with_usage(&format!("Unknown option: {}", flag)),
+        }
+    }
+
+    let mut entrants = vec![
+        Entrant::new("random", RandomPlayer::new(simulation.seed)),
+        Entrant::new("calling station", CallingStation),
+        Entrant::new("rule based", RuleBasedPlayer),
+    ];
+
+    let report = match tournaments {
+        Some(tournaments) => simulation.sit_and_go(&mut entrants, tournaments),
+        None => simulation.cash_game(&mut entrants, hands),
+    };
+
+    match report {
+        Ok(report) => print!("{}", report),
+        Err(error) => {
+            eprintln!("{}", error);
+            process::exit(1)
+        }
+    }
+}
+
+fn exit_with_usage(message: &str) -> ! {
+    eprintln!("{}\n{}", message, USAGE);
+    process::exit(2)
+}
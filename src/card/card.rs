@@ -1,10 +1,11 @@
-use std::collections::HashSet;
 use std::{fmt, str::FromStr};
 
+use rand::{rngs::StdRng, Rng, SeedableRng};
 use serde::Serialize;
 use serde_with::DeserializeFromStr;
 
 use super::cardvalue::CardValue;
+use super::deck::Deck;
 use super::suit::Suit;
 
 #[derive(Debug, PartialEq, Eq)]
@@ -34,7 +35,10 @@ pub struct Card {
 
 impl fmt::Display for Card {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{}{}", self.value, self.suit)
+        match self.value {
+            CardValue::Joker => write!(f, "{}", self.value),
+            _ => write!(f, "{}{}", self.value, self.suit),
+        }
     }
 }
 
@@ -46,6 +50,10 @@ impl FromStr for Card {
             return Err(CardError::InvalidCard);
         }
 
+        if s == "jo" {
+            return Ok(Card::JOKER);
+        }
+
         let as_char: Vec<char> = s.chars().collect();
         let value = CardValue::from_str(&String::from(as_char[0]))?;
         let suit = Suit::from_str(&String::from(as_char[1]))?;
@@ -55,16 +63,42 @@ impl FromStr for Card {
 }
 
 impl Card {
-    pub fn new_hand() -> Vec<Card> {
-        let mut cards = HashSet::new();
-        while cards.len() != 5 {
-            cards.insert(Card {
-                value: rand::random(),
-                suit: rand::random(),
-            });
+    // There is only one joker, the suit is only there because every card has one
+    pub const JOKER: Card = Card {
+        value: CardValue::Joker,
+        suit: Suit::Heart,
+    };
+
+    pub fn is_joker(&self) -> bool {
+        self.value == CardValue::Joker
+    }
+
+    // A unique number between 0 and 51 for each card in the deck, and 52 for the joker
+    pub fn index(&self) -> usize {
+        (self.value as usize - 2) * 4 + self.suit as usize
+    }
+
+    pub fn random<R: Rng + ?Sized>(rng: &mut R) -> Card {
+        Card {
+            value: rng.gen(),
+            suit: rng.gen(),
         }
+    }
 
-        cards.into_iter().collect()
+    pub fn new_hand() -> Vec<Card> {
+        Card::new_hand_with_rng(&mut rand::thread_rng())
+    }
+
+    // The same seed always deals the same hand
+    pub fn new_hand_from_seed(seed: u64) -> Vec<Card> {
+        Card::new_hand_with_rng(&mut StdRng::seed_from_u64(seed))
+    }
+
+    pub fn new_hand_with_rng<R: Rng + ?Sized>(rng: &mut R) -> Vec<Card> {
+        let mut deck = Deck::new();
+        deck.shuffle(rng);
+
+        deck.deal(5).expect("a full deck has more than five cards")
     }
 }
 
@@ -77,7 +111,7 @@ mod tests {
 
     #[test]
     fn test_new_hand_no_duplicates() {
-        for _ in 0..100_00 {
+        for _ in 0..10_000 {
             let mut hand: Vec<Card> = Card::new_hand();
             hand.sort();
             hand.dedup();
@@ -86,6 +120,58 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_new_hand_from_seed() {
+        let hand_one = Card::new_hand_from_seed(1234);
+        let hand_two = Card::new_hand_from_seed(1234);
+        let hand_three = Card::new_hand_from_seed(4321);
+
+        assert_eq!(hand_one, hand_two);
+        assert_ne!(hand_one, hand_three)
+    }
+
+    #[test]
+    fn test_random_card_is_uniform() {
+        let mut rng = StdRng::seed_from_u64(9);
+        let draws = 520_000;
+
+        let mut counts = [0; 52];
+        for _ in 0..draws {
+            counts[Card::random(&mut rng).index()] += 1;
+        }
+
+        // With 51 degrees of freedom a chi-squared value above 86.66 happens less than
+        // 0.1% of the time when every card is equally likely
+        let expected = draws as f64 / 52.0;
+        let chi_squared: f64 = counts
+            .iter()
+            .map(|&count| (count as f64 - expected).powi(2) / expected)
+            .sum();
+
+        assert!(chi_squared < 86.66, "chi-squared was {chi_squared}")
+    }
+
+    #[test]
+    fn test_new_hand_is_uniform() {
+        let mut rng = StdRng::seed_from_u64(9);
+        let hands = 104_000;
+
+        let mut counts = [0; 52];
+        for _ in 0..hands {
+            for card in Card::new_hand_with_rng(&mut rng) {
+                counts[card.index()] += 1;
+            }
+        }
+
+        let expected = hands as f64 * 5.0 / 52.0;
+        let chi_squared: f64 = counts
+            .iter()
+            .map(|&count| (count as f64 - expected).powi(2) / expected)
+            .sum();
+
+        assert!(chi_squared < 86.66, "chi-squared was {chi_squared}")
+    }
+
     #[test]
     fn test_card_to_string() {
         let card_one = Card {
@@ -120,4 +206,14 @@ mod tests {
         assert_eq!(card_suit_err, CardError::UnknownSuit);
         assert_eq!(card_invalid_err, CardError::InvalidCard);
     }
+
+    #[test]
+    fn test_joker() {
+        let joker = Card::from_str("jo").unwrap();
+
+        assert_eq!(joker, Card::JOKER);
+        assert!(joker.is_joker());
+        assert_eq!(joker.to_string(), "jo");
+        assert_eq!(joker.index(), 52)
+    }
 }
@@ -21,25 +21,69 @@ pub enum CardValue {
     Q,
     K,
     A,
+    // The joker only exists in decks with jokers and is not a part of `CardValue::ALL`
+    Joker,
+}
+
+impl CardValue {
+    pub const ALL: [CardValue; 13] = [
+        CardValue::Two,
+        CardValue::Three,
+        CardValue::Four,
+        CardValue::Five,
+        CardValue::Six,
+        CardValue::Seven,
+        CardValue::Eigth,
+        CardValue::Nine,
+        CardValue::T,
+        CardValue::J,
+        CardValue::Q,
+        CardValue::K,
+        CardValue::A,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            CardValue::Two => "two",
+            CardValue::Three => "three",
+            CardValue::Four => "four",
+            CardValue::Five => "five",
+            CardValue::Six => "six",
+            CardValue::Seven => "seven",
+            CardValue::Eigth => "eight",
+            CardValue::Nine => "nine",
+            CardValue::T => "ten",
+            CardValue::J => "jack",
+            CardValue::Q => "queen",
+            CardValue::K => "king",
+            CardValue::A => "ace",
+            CardValue::Joker => "joker",
+        }
+    }
+
+    pub fn plural_name(&self) -> &'static str {
+        match self {
+            CardValue::Two => "twos",
+            CardValue::Three => "threes",
+            CardValue::Four => "fours",
+            CardValue::Five => "fives",
+            CardValue::Six => "sixes",
+            CardValue::Seven => "sevens",
+            CardValue::Eigth => "eights",
+            CardValue::Nine => "nines",
+            CardValue::T => "tens",
+            CardValue::J => "jacks",
+            CardValue::Q => "queens",
+            CardValue::K => "kings",
+            CardValue::A => "aces",
+            CardValue::Joker => "jokers",
+        }
+    }
 }
 
 impl Distribution<CardValue> for Standard {
     fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> CardValue {
-        match rng.gen_range(0..=13) {
-            2 => CardValue::Two,
-            3 => CardValue::Three,
-            4 => CardValue::Four,
-            5 => CardValue::Five,
-            6 => CardValue::Six,
-            7 => CardValue::Seven,
-            8 => CardValue::Eigth,
-            9 => CardValue::Nine,
-            10 => CardValue::T,
-            11 => CardValue::J,
-            12 => CardValue::Q,
-            13 => CardValue::K,
-            _ => CardValue::A,
-        }
+        CardValue::ALL[rng.gen_range(0..CardValue::ALL.len())]
     }
 }
 
@@ -59,6 +103,7 @@ impl Display for CardValue {
             CardValue::Q => write!(f, "q"),
             CardValue::K => write!(f, "k"),
             CardValue::A => write!(f, "a"),
+            CardValue::Joker => write!(f, "jo"),
         }
     }
 }
@@ -90,6 +135,8 @@ impl FromStr for CardValue {
 mod tests {
     use std::str::FromStr;
 
+    use rand::{rngs::StdRng, Rng, SeedableRng};
+
     use crate::card::{card::CardError, cardvalue::CardValue};
 
     #[test]
@@ -123,6 +170,13 @@ mod tests {
         assert_eq!(ace.to_string(), "a");
     }
 
+    #[test]
+    fn test_value_names() {
+        assert_eq!(CardValue::Six.name(), "six");
+        assert_eq!(CardValue::Six.plural_name(), "sixes");
+        assert_eq!(CardValue::A.plural_name(), "aces")
+    }
+
     #[test]
     fn test_card_value_fromstr() {
         let card_value_ok = CardValue::from_str("3").unwrap();
@@ -131,4 +185,26 @@ mod tests {
         assert_eq!(card_value_ok, CardValue::Three);
         assert_eq!(card_value_err, CardError::UnknownCardValue);
     }
+
+    #[test]
+    fn test_card_value_distribution_is_uniform() {
+        let mut rng = StdRng::seed_from_u64(9);
+        let draws = 130_000;
+
+        let mut counts = [0; 13];
+        for _ in 0..draws {
+            let value: CardValue = rng.gen();
+            counts[value as usize - 2] += 1;
+        }
+
+        // With 12 degrees of freedom a chi-squared value above 32.91 happens less than
+        // 0.1% of the time when every card value is equally likely
+        let expected = draws as f64 / 13.0;
+        let chi_squared: f64 = counts
+            .iter()
+            .map(|&count| (count as f64 - expected).powi(2) / expected)
+            .sum();
+
+        assert!(chi_squared < 32.91, "chi-squared was {chi_squared}")
+    }
 }
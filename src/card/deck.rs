@@ -0,0 +1,240 @@
+use std::fmt;
+
+use rand::{rngs::StdRng, seq::SliceRandom, Rng, SeedableRng};
+
+use super::{card::Card, cardvalue::CardValue, suit::Suit};
+use crate::hand_ranks::Rules;
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum DeckError {
+    NotEnoughCards,
+    CardNotInDeck,
+}
+
+impl fmt::Display for DeckError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DeckError::NotEnoughCards => write!(f, "Not enough cards left in the deck"),
+            DeckError::CardNotInDeck => write!(f, "Card is not in the deck"),
+        }
+    }
+}
+
+// A deck of cards where the top of the deck is the last card. Cards are dealt and burned from
+// the top, so cards that have been dealt can never be dealt again.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Deck {
+    cards: Vec<Card>,
+}
+
+impl Default for Deck {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Deck {
+    // A full, unshuffled deck with all 52 cards
+    pub fn new() -> Deck {
+        Deck::with_rules(Rules::Standard)
+    }
+
+    // A full, unshuffled deck with the cards played under the rules, so 36 cards for short-deck
+    pub fn with_rules(rules: Rules) -> Deck {
+        let cards = CardValue::ALL
+            .iter()
+            .filter(|&&value| rules.includes(value))
+            .flat_map(|&value| Suit::ALL.iter().map(move |&suit| Card { value, suit }))
+            .collect();
+
+        Deck { cards }
+    }
+
+    pub fn shuffle<R: Rng + ?Sized>(&mut self, rng: &mut R) {
+        self.cards.shuffle(rng);
+    }
+
+    // Shuffling a full deck with the same seed always gives the same order
+    pub fn shuffle_with_seed(&mut self, seed: u64) {
+        self.shuffle(&mut StdRng::seed_from_u64(seed));
+    }
+
+    // A deck that deals the cards in the order they are given, to replay a hand or to stack the
+    // deck in a test
+    pub fn from_cards(cards: Vec<Card>) -> Deck {
+        let mut cards = cards;
+        cards.reverse();
+
+        Deck { cards }
+    }
+
+    // A full, unshuffled deck with all 52 cards and the joker
+    pub fn with_joker() -> Deck {
+        let mut deck = Deck::new();
+        deck.cards.push(Card::JOKER);
+
+        deck
+    }
+
+    // Deals `n` cards from the top of the deck, in the order they were dealt
+    pub fn deal(&mut self, n: usize) -> Result<Vec<Card>, DeckError> {
+        if n > self.cards.len() {
+            return Err(DeckError::NotEnoughCards);
+        }
+
+        let mut cards = self.cards.split_off(self.cards.len() - n);
+        cards.reverse();
+
+        Ok(cards)
+    }
+
+    // Removes the top card of the deck without showing it
+    pub fn burn(&mut self) -> Result<(), DeckError> {
+        self.cards
+            .pop()
+            .map(|_| ())
+            .ok_or(DeckError::NotEnoughCards)
+    }
+
+    // Removes cards that are already known, like the hole cards of a player or a board,
+    // so they are not dealt again
+    pub fn remove(&mut self, cards: &[Card]) -> Result<(), DeckError> {
+        if !cards.iter().all(|card| self.cards.contains(card)) {
+            return Err(DeckError::CardNotInDeck);
+        }
+
+        self.cards.retain(|card| !cards.contains(card));
+
+        Ok(())
+    }
+
+    pub fn remaining(&self) -> usize {
+        self.cards.len()
+    }
+
+    pub fn cards(&self) -> &[Card] {
+        &self.cards
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use rand::{rngs::StdRng, SeedableRng};
+
+    use super::*;
+
+    #[test]
+    fn test_new_deck() {
+        let deck = Deck::new();
+
+        let mut cards = deck.cards().to_vec();
+        cards.sort();
+        cards.dedup();
+
+        assert_eq!(deck.remaining(), 52);
+        assert_eq!(cards.len(), 52)
+    }
+
+    #[test]
+    fn test_short_deck() {
+        let mut deck = Deck::with_rules(Rules::ShortDeck);
+        deck.shuffle_with_seed(5);
+
+        let cards = deck.deal(36).unwrap();
+
+        assert_eq!(cards.len(), 36);
+        assert!(cards.iter().all(|card| card.value >= CardValue::Six));
+        assert_eq!(deck.deal(1).unwrap_err(), DeckError::NotEnoughCards)
+    }
+
+    #[test]
+    fn test_deck_with_joker() {
+        let deck = Deck::with_joker();
+
+        assert_eq!(deck.remaining(), 53);
+        assert!(deck.cards().contains(&Card::JOKER))
+    }
+
+    #[test]
+    fn test_deal_table_without_duplicates() {
+        let mut deck = Deck::new();
+        deck.shuffle(&mut StdRng::seed_from_u64(1));
+
+        let mut dealt: Vec<Card> = Vec::new();
+        for _ in 0..9 {
+            dealt.extend(deck.deal(2).unwrap());
+        }
+        deck.burn().unwrap();
+        dealt.extend(deck.deal(3).unwrap());
+        deck.burn().unwrap();
+        dealt.extend(deck.deal(1).unwrap());
+        deck.burn().unwrap();
+        dealt.extend(deck.deal(1).unwrap());
+
+        assert_eq!(deck.remaining(), 52 - 23 - 3);
+        assert!(dealt.iter().all(|card| !deck.cards().contains(card)));
+
+        dealt.sort();
+        dealt.dedup();
+        assert_eq!(dealt.len(), 23)
+    }
+
+    #[test]
+    fn test_shuffle_with_seed() {
+        let mut deck_one = Deck::new();
+        let mut deck_two = Deck::new();
+        let mut deck_three = Deck::new();
+
+        deck_one.shuffle_with_seed(99);
+        deck_two.shuffle_with_seed(99);
+        deck_three.shuffle_with_seed(100);
+
+        assert_eq!(deck_one, deck_two);
+        assert_ne!(deck_one, deck_three)
+    }
+
+    #[test]
+    fn test_deal_from_top() {
+        let mut deck = Deck::new();
+        let top: Vec<Card> = deck.cards().iter().rev().take(2).cloned().collect();
+
+        assert_eq!(deck.deal(2).unwrap(), top)
+    }
+
+    #[test]
+    fn test_deck_from_cards() {
+        let cards: Vec<Card> = ["ah", "2s", "tk"]
+            .iter()
+            .map(|c| Card::from_str(c).unwrap())
+            .collect();
+        let mut deck = Deck::from_cards(cards.clone());
+
+        assert_eq!(deck.deal(3).unwrap(), cards)
+    }
+
+    #[test]
+    fn test_remove_known_cards() {
+        let mut deck = Deck::new();
+        let known: Vec<Card> = ["ah", "kh"].map(|c| Card::from_str(c).unwrap()).to_vec();
+
+        deck.remove(&known).unwrap();
+
+        assert_eq!(deck.remaining(), 50);
+        assert!(!deck.cards().contains(&known[0]));
+        assert_eq!(deck.remove(&known).unwrap_err(), DeckError::CardNotInDeck);
+        assert_eq!(deck.remaining(), 50)
+    }
+
+    #[test]
+    fn test_not_enough_cards() {
+        let mut deck = Deck::new();
+
+        assert_eq!(deck.deal(53).unwrap_err(), DeckError::NotEnoughCards);
+
+        deck.deal(52).unwrap();
+
+        assert_eq!(deck.burn().unwrap_err(), DeckError::NotEnoughCards)
+    }
+}
@@ -1,3 +1,5 @@
+#[allow(clippy::module_inception)]
 pub mod card;
 pub mod cardvalue;
+pub mod deck;
 pub mod suit;
@@ -14,14 +14,13 @@ pub enum Suit {
     Club,
 }
 
+impl Suit {
+    pub const ALL: [Suit; 4] = [Suit::Heart, Suit::Spade, Suit::Diamond, Suit::Club];
+}
+
 impl Distribution<Suit> for Standard {
     fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Suit {
-        match rng.gen_range(0..=3) {
-            1 => Suit::Heart,
-            2 => Suit::Spade,
-            3 => Suit::Diamond,
-            _ => Suit::Club,
-        }
+        Suit::ALL[rng.gen_range(0..Suit::ALL.len())]
     }
 }
 
@@ -54,6 +53,8 @@ impl FromStr for Suit {
 mod tests {
     use std::str::FromStr;
 
+    use rand::{rngs::StdRng, Rng, SeedableRng};
+
     use crate::card::{card::CardError, suit::Suit};
 
     #[test]
@@ -77,4 +78,26 @@ mod tests {
         assert_eq!(suit_ok, Suit::Diamond);
         assert_eq!(suit_err, CardError::UnknownSuit);
     }
+
+    #[test]
+    fn test_suit_distribution_is_uniform() {
+        let mut rng = StdRng::seed_from_u64(9);
+        let draws = 40_000;
+
+        let mut counts = [0; 4];
+        for _ in 0..draws {
+            let suit: Suit = rng.gen();
+            counts[suit as usize] += 1;
+        }
+
+        // With 3 degrees of freedom a chi-squared value above 16.27 happens less than
+        // 0.1% of the time when every suit is equally likely
+        let expected = draws as f64 / 4.0;
+        let chi_squared: f64 = counts
+            .iter()
+            .map(|&count| (count as f64 - expected).powi(2) / expected)
+            .sum();
+
+        assert!(chi_squared < 16.27, "chi-squared was {chi_squared}")
+    }
 }
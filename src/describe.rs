@@ -0,0 +1,207 @@
+use std::fmt::Display;
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    card::{card::Card, cardvalue::CardValue},
+    eval::BestHand,
+    hand_ranks::{HandRanking, HandValue},
+};
+
+// Explains a made hand: which card values make the ranking, which are kickers, and the cards
+// that play each role. Displays as a name like "Two pair, kings and jacks, three kicker".
+//
+// For two pair kings and jacks with a three the primary values are [K, J] and the kicker is
+// [Three]. Straights, flushes and full houses use all five cards, so they have no kickers.
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone)]
+pub struct HandDescription {
+    pub value: HandValue,
+    pub primary: Vec<CardValue>,
+    pub kickers: Vec<CardValue>,
+    // The cards are ordered like the values, the most important first
+    pub primary_cards: Vec<Card>,
+    pub kicker_cards: Vec<Card>,
+}
+
+impl HandDescription {
+    pub fn from(best_hand: &BestHand) -> HandDescription {
+        let value = best_hand.value.clone();
+
+        let primary_count = match value.ranking {
+            HandRanking::HighCard
+            | HandRanking::Pair
+            | HandRanking::ThreeOfAKind
+            | HandRanking::FourOfAKind => 1,
+            HandRanking::TwoPair => 2,
+            _ => value.ranks.len(),
+        };
+        let (primary, kickers) = value.ranks.split_at(primary_count.min(value.ranks.len()));
+
+        // Wild cards have a value that isn't part of the ranks, they always help make the ranking
+        let position = |card: &Card| {
+            value
+                .ranks
+                .iter()
+                .position(|&rank| rank == card.value)
+                .unwrap_or(value.ranks.len())
+        };
+
+        let mut cards = best_hand.cards.clone();
+        cards.sort_by_key(|card| (position(card), *card));
+        let (kicker_cards, primary_cards): (Vec<Card>, Vec<Card>) = cards
+            .into_iter()
+            .partition(|card| kickers.contains(&card.value));
+
+        HandDescription {
+            primary: primary.to_vec(),
+            kickers: kickers.to_vec(),
+            primary_cards,
+            kicker_cards,
+            value,
+        }
+    }
+
+    fn write_kicker(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.kickers.first() {
+            Some(kicker) => write!(f, ", {} kicker", kicker.name()),
+            None => Ok(()),
+        }
+    }
+}
+
+impl Display for HandDescription {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let ranking = self.value.ranking;
+        write!(f, "{}", ranking)?;
+
+        let (Some(first), second) = (self.primary.first(), self.primary.get(1)) else {
+            return Ok(());
+        };
+
+        match (ranking, second) {
+            (HandRanking::RoyalStraightFlush, _) => Ok(()),
+            (HandRanking::TwoPair, Some(second)) => {
+                write!(f, ", {} and {}", first.plural_name(), second.plural_name())?;
+                self.write_kicker(f)
+            }
+            (HandRanking::FullHouse, Some(second)) => {
+                write!(
+                    f,
+                    ", {} full of {}",
+                    first.plural_name(),
+                    second.plural_name()
+                )
+            }
+            (HandRanking::Straight | HandRanking::StraightFlush | HandRanking::Flush, _) => {
+                write!(f, ", {} high", first.name())
+            }
+            (HandRanking::HighCard, _) => write!(f, ", {}", first.name()),
+            _ => {
+                write!(f, ", {}", first.plural_name())?;
+                self.write_kicker(f)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use crate::{eval::Eval, hand_ranks::Rules, wild};
+
+    use super::*;
+
+    fn cards(cards: &[&str]) -> Vec<Card> {
+        cards.iter().map(|c| Card::from_str(c).unwrap()).collect()
+    }
+
+    fn describe(hand: &[&str]) -> HandDescription {
+        Eval::from(cards(hand)).describe().unwrap()
+    }
+
+    #[test]
+    fn test_describe_two_pair() {
+        let description = describe(&["kh", "3s", "jr", "ks", "jh", "2r", "2h"]);
+
+        assert_eq!(description.primary, vec![CardValue::K, CardValue::J]);
+        assert_eq!(description.kickers, vec![CardValue::Three]);
+        assert_eq!(description.primary_cards, cards(&["kh", "ks", "jh", "jr"]));
+        assert_eq!(description.kicker_cards, cards(&["3s"]));
+        assert_eq!(
+            description.to_string(),
+            "Two pair, kings and jacks, three kicker"
+        )
+    }
+
+    #[test]
+    fn test_describe_pair() {
+        let description = describe(&["6h", "6s", "ar", "9k", "4h"]);
+
+        assert_eq!(description.primary_cards, cards(&["6h", "6s"]));
+        assert_eq!(description.kicker_cards, cards(&["ar", "9k", "4h"]));
+        assert_eq!(description.to_string(), "Pair, sixes, ace kicker")
+    }
+
+    #[test]
+    fn test_describe_names() {
+        let hands = [
+            (vec!["ah", "qs", "9r", "7k", "4h"], "High card, ace"),
+            (
+                vec!["7h", "7s", "7r", "kk", "2h"],
+                "Three of a kind, sevens, king kicker",
+            ),
+            (vec!["9h", "8s", "7r", "6k", "5h"], "Straight, nine high"),
+            (vec!["ah", "2s", "3r", "4k", "5h"], "Straight, five high"),
+            (vec!["ah", "jh", "8h", "6h", "2h"], "Flush, ace high"),
+            (
+                vec!["kh", "ks", "kr", "jk", "jh"],
+                "Full house, kings full of jacks",
+            ),
+            (
+                vec!["qh", "qs", "qr", "qk", "2h"],
+                "Four of a kind, queens, two kicker",
+            ),
+            (
+                vec!["9h", "8h", "7h", "6h", "5h"],
+                "Straight flush, nine high",
+            ),
+            (vec!["ah", "kh", "qh", "jh", "th"], "Royal straight flush"),
+        ];
+
+        for (hand, name) in hands {
+            assert_eq!(describe(&hand).to_string(), name)
+        }
+    }
+
+    #[test]
+    fn test_describe_made_hands_have_no_kickers() {
+        let straight = describe(&["ah", "2s", "3r", "4k", "5h"]);
+
+        assert!(straight.kickers.is_empty());
+        assert!(straight.kicker_cards.is_empty());
+        assert_eq!(
+            straight.primary_cards,
+            cards(&["5h", "4k", "3r", "2s", "ah"])
+        )
+    }
+
+    #[test]
+    fn test_describe_short_deck_straight() {
+        let description =
+            Eval::with_rules(cards(&["ah", "6s", "7r", "8k", "9h"]), Rules::ShortDeck)
+                .describe()
+                .unwrap();
+
+        assert_eq!(description.to_string(), "Straight, nine high")
+    }
+
+    #[test]
+    fn test_describe_wild_hand() {
+        let best_hand = wild::evaluate_best(&cards(&["jo", "ah", "as", "ar", "ak"]), &[]).unwrap();
+        let description = HandDescription::from(&best_hand);
+
+        assert_eq!(description.to_string(), "Five of a kind, aces");
+        assert_eq!(description.primary_cards.len(), 5)
+    }
+}
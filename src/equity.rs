@@ -0,0 +1,665 @@
+use rand::Rng;
+
+use crate::{
+    card::{card::Card, cardvalue::CardValue, deck::Deck, suit::Suit},
+    eval::{validate_cards, HandError},
+    lookup::{self, HandStrength},
+    range::{Combo, Range},
+};
+
+#[derive(Debug, PartialEq, Clone, Default)]
+pub struct EquityResult {
+    // Boards where the player had the best hand alone
+    pub wins: u64,
+    // Boards where the player shared the best hand with other players
+    pub ties: u64,
+    pub losses: u64,
+    // The sum of the part of the pot won on each board, a tie between two players counts as 0.5
+    pub pot_share: f64,
+}
+
+impl EquityResult {
+    pub fn boards(&self) -> u64 {
+        self.wins + self.ties + self.losses
+    }
+
+    pub fn win_percentage(&self) -> f64 {
+        self.percentage(self.wins as f64)
+    }
+
+    pub fn tie_percentage(&self) -> f64 {
+        self.percentage(self.ties as f64)
+    }
+
+    pub fn loss_percentage(&self) -> f64 {
+        self.percentage(self.losses as f64)
+    }
+
+    // The percentage of the pot the player wins on average, counting ties as a part of the pot
+    pub fn equity(&self) -> f64 {
+        self.percentage(self.pot_share)
+    }
+
+    fn percentage(&self, count: f64) -> f64 {
+        if self.boards() == 0 {
+            return 0.0;
+        }
+
+        100.0 * count / self.boards() as f64
+    }
+}
+
+// The equity of a whole range against other ranges. Each matchup of combos counts with the product
+// of the weights of the combos, so these are weighted sums of boards rather than board counts.
+#[derive(Debug, PartialEq, Clone, Default)]
+pub struct RangeEquity {
+    pub wins: f64,
+    pub ties: f64,
+    pub losses: f64,
+    pub pot_share: f64,
+}
+
+impl RangeEquity {
+    pub fn total(&self) -> f64 {
+        self.wins + self.ties + self.losses
+    }
+
+    pub fn win_percentage(&self) -> f64 {
+        self.percentage(self.wins)
+    }
+
+    pub fn tie_percentage(&self) -> f64 {
+        self.percentage(self.ties)
+    }
+
+    pub fn loss_percentage(&self) -> f64 {
+        self.percentage(self.losses)
+    }
+
+    pub fn equity(&self) -> f64 {
+        self.percentage(self.pot_share)
+    }
+
+    fn percentage(&self, amount: f64) -> f64 {
+        if self.total() == 0.0 {
+            return 0.0;
+        }
+
+        100.0 * amount / self.total()
+    }
+
+    fn add(&mut self, result: &EquityResult, weight: f64) {
+        self.wins += weight * result.wins as f64;
+        self.ties += weight * result.ties as f64;
+        self.losses += weight * result.losses as f64;
+        self.pot_share += weight * result.pot_share;
+    }
+}
+
+// Deals the rest of the board `iterations` times and counts how often each player wins, ties
+// and loses. Pass a seeded rng, like `StdRng::seed_from_u64`, to get the same result every time.
+pub fn simulate<R: Rng + ?Sized>(
+    players: &[Vec<Card>],
+    board: &[Card],
+    iterations: usize,
+    rng: &mut R,
+) -> Result<Vec<EquityResult>, HandError> {
+    validate(players, board)?;
+
+    let dead_cards = card_mask(players.iter().flatten().chain(board));
+    let mut full_board = [Card {
+        value: CardValue::A,
+        suit: Suit::Heart,
+    }; 5];
+    full_board[..board.len()].copy_from_slice(board);
+
+    let mut results = vec![EquityResult::default(); players.len()];
+    let mut strengths = Vec::with_capacity(players.len());
+
+    for _ in 0..iterations {
+        deal_board(&mut full_board, board.len(), dead_cards, rng);
+        settle(players, &full_board, &mut strengths, &mut results);
+    }
+
+    Ok(results)
+}
+
+// Like `simulate`, but each player is dealt a random combo out of their range every iteration.
+// Combos that share a card with the board or with another player's combo are never dealt
+// together, and a combo with a weight is dealt that much less often.
+pub fn simulate_ranges<R: Rng + ?Sized>(
+    ranges: &[Range],
+    board: &[Card],
+    iterations: usize,
+    rng: &mut R,
+) -> Result<Vec<RangeEquity>, HandError> {
+    let ranges = live_combos(ranges, board)?;
+
+    let board_cards = card_mask(board.iter());
+    let mut full_board = [Card {
+        value: CardValue::A,
+        suit: Suit::Heart,
+    }; 5];
+    full_board[..board.len()].copy_from_slice(board);
+
+    let mut players = vec![Vec::with_capacity(2); ranges.len()];
+    let mut results = vec![EquityResult::default(); ranges.len()];
+    let mut strengths = Vec::with_capacity(ranges.len());
+
+    for _ in 0..iterations {
+        let dead_cards = loop {
+            if let Some(dead_cards) = deal_combos(&ranges, board_cards, &mut players, rng) {
+                break dead_cards;
+            }
+        };
+
+        deal_board(&mut full_board, board.len(), dead_cards, rng);
+        settle(&players, &full_board, &mut strengths, &mut results);
+    }
+
+    Ok(results
+        .iter()
+        .map(|result| {
+            let mut equity = RangeEquity::default();
+            equity.add(result, 1.0);
+            equity
+        })
+        .collect())
+}
+
+// Like `enumerate`, but for every matchup of combos out of the ranges that don't share a card
+// with each other or the board. This multiplies the number of boards by the number of matchups,
+// so it is best used with a flop or more on the board.
+pub fn enumerate_ranges(ranges: &[Range], board: &[Card]) -> Result<Vec<RangeEquity>, HandError> {
+    let ranges = live_combos(ranges, board)?;
+
+    let mut results = vec![RangeEquity::default(); ranges.len()];
+    let mut players = Vec::with_capacity(ranges.len());
+
+    enumerate_matchups(
+        &ranges,
+        board,
+        card_mask(board.iter()),
+        1.0,
+        &mut players,
+        &mut results,
+    );
+
+    Ok(results)
+}
+
+fn enumerate_matchups(
+    ranges: &[Vec<Combo>],
+    board: &[Card],
+    dead_cards: u64,
+    weight: f64,
+    players: &mut Vec<Vec<Card>>,
+    results: &mut [RangeEquity],
+) {
+    let Some((range, rest)) = ranges.split_first() else {
+        let matchup = enumerate(players, board).expect("combos don't share cards");
+        for (result, equity) in results.iter_mut().zip(&matchup) {
+            result.add(equity, weight);
+        }
+        return;
+    };
+
+    for combo in range {
+        let combo_cards = card_mask(combo.cards.iter());
+        if dead_cards & combo_cards != 0 {
+            continue;
+        }
+
+        players.push(combo.cards.to_vec());
+        enumerate_matchups(
+            rest,
+            board,
+            dead_cards | combo_cards,
+            weight * combo.weight,
+            players,
+            results,
+        );
+        players.pop();
+    }
+}
+
+// Fills the board after the cards that are already dealt with random cards that aren't dead
+fn deal_board<R: Rng + ?Sized>(board: &mut [Card; 5], dealt: usize, dead_cards: u64, rng: &mut R) {
+    let mut used_cards = dead_cards;
+    for card in board.iter_mut().skip(dealt) {
+        *card = loop {
+            let card = Card::random(rng);
+            if used_cards & (1 << card.index()) == 0 {
+                break card;
+            }
+        };
+        used_cards |= 1 << card.index();
+    }
+}
+
+// Picks a random combo out of each range and returns every card in use. Gives up and returns
+// `None` when the combos share a card, or when a combo is left out because of its weight, so
+// trying again until it succeeds deals each matchup as often as the weights say.
+fn deal_combos<R: Rng + ?Sized>(
+    ranges: &[Vec<Combo>],
+    board_cards: u64,
+    players: &mut [Vec<Card>],
+    rng: &mut R,
+) -> Option<u64> {
+    let mut used_cards = board_cards;
+
+    for (range, hole_cards) in ranges.iter().zip(players.iter_mut()) {
+        let combo = range[rng.gen_range(0..range.len())];
+        let combo_cards = card_mask(combo.cards.iter());
+
+        if used_cards & combo_cards != 0 || rng.gen::<f64>() >= combo.weight {
+            return None;
+        }
+
+        used_cards |= combo_cards;
+        hole_cards.clear();
+        hole_cards.extend(combo.cards);
+    }
+
+    Some(used_cards)
+}
+
+// The combos of each range that don't use a card on the board. When there is no way to deal a
+// combo to every player without two of them sharing a card, the ranges are treated as
+// duplicate cards.
+fn live_combos(ranges: &[Range], board: &[Card]) -> Result<Vec<Vec<Combo>>, HandError> {
+    validate_cards(board, 0, 5)?;
+
+    if 2 * ranges.len() + 5 > 52 {
+        return Err(HandError::TooManyCards);
+    }
+
+    // Parsed ranges only hold real hands, but the combos of a range built by hand can be anything
+    for combo in ranges.iter().flat_map(|range| &range.combos) {
+        validate_cards(&combo.cards, 2, 2)?;
+    }
+
+    let board_cards = card_mask(board.iter());
+    let live: Vec<Vec<Combo>> = ranges
+        .iter()
+        .map(|range| {
+            range
+                .combos
+                .iter()
+                // The combos of a range built by hand can have any weight, and one that is never
+                // played would be dealt forever by `simulate_ranges`
+                .filter(|combo| combo.weight > 0.0)
+                .filter(|combo| card_mask(combo.cards.iter()) & board_cards == 0)
+                .cloned()
+                .collect()
+        })
+        .collect();
+
+    if !has_matchup(&live, board_cards) {
+        return Err(HandError::DuplicateCards);
+    }
+
+    Ok(live)
+}
+
+fn has_matchup(ranges: &[Vec<Combo>], dead_cards: u64) -> bool {
+    let Some((range, rest)) = ranges.split_first() else {
+        return true;
+    };
+
+    range.iter().any(|combo| {
+        let combo_cards = card_mask(combo.cards.iter());
+        dead_cards & combo_cards == 0 && has_matchup(rest, dead_cards | combo_cards)
+    })
+}
+
+// Deals every possible rest of the board once, so the results are exact. The number of boards
+// grows fast with the number of missing board cards, a flop has 990 boards left to deal
+// heads-up while a preflop spot has 1,712,304.
+pub fn enumerate(players: &[Vec<Card>], board: &[Card]) -> Result<Vec<EquityResult>, HandError> {
+    validate(players, board)?;
+
+    let known_cards: Vec<Card> = players.iter().flatten().chain(board).cloned().collect();
+    let mut deck = Deck::new();
+    deck.remove(&known_cards)
+        .expect("known cards are validated to be unique");
+
+    let mut full_board = [Card {
+        value: CardValue::A,
+        suit: Suit::Heart,
+    }; 5];
+    full_board[..board.len()].copy_from_slice(board);
+
+    let mut results = vec![EquityResult::default(); players.len()];
+    let mut strengths = Vec::with_capacity(players.len());
+
+    enumerate_boards(
+        players,
+        deck.cards(),
+        board.len(),
+        &mut full_board,
+        &mut strengths,
+        &mut results,
+    );
+
+    Ok(results)
+}
+
+fn enumerate_boards(
+    players: &[Vec<Card>],
+    unseen: &[Card],
+    dealt: usize,
+    board: &mut [Card; 5],
+    strengths: &mut Vec<HandStrength>,
+    results: &mut [EquityResult],
+) {
+    if dealt == 5 {
+        settle(players, board, strengths, results);
+        return;
+    }
+
+    for (i, &card) in unseen.iter().enumerate() {
+        board[dealt] = card;
+        enumerate_boards(
+            players,
+            &unseen[i + 1..],
+            dealt + 1,
+            board,
+            strengths,
+            results,
+        );
+    }
+}
+
+pub(crate) fn validate(players: &[Vec<Card>], board: &[Card]) -> Result<(), HandError> {
+    if board.len() > 5 {
+        return Err(HandError::TooManyCards);
+    }
+
+    for hole_cards in players {
+        validate_cards(hole_cards, 2, 2)?;
+    }
+
+    let all_cards: Vec<Card> = players.iter().flatten().chain(board).cloned().collect();
+    validate_cards(&all_cards, 0, 52 - (5 - board.len()))
+}
+
+// One bit for each card, as given by `Card::index`
+pub(crate) fn card_mask<'a>(cards: impl Iterator<Item = &'a Card>) -> u64 {
+    cards.fold(0, |mask, card| mask | (1 << card.index()))
+}
+
+// Evaluates every player on a complete board and adds the outcome to their results
+pub(crate) fn settle(
+    players: &[Vec<Card>],
+    board: &[Card; 5],
+    strengths: &mut Vec<HandStrength>,
+    results: &mut [EquityResult],
+) {
+    strengths.clear();
+    for hole_cards in players {
+        let cards = [
+            hole_cards[0],
+            hole_cards[1],
+            board[0],
+            board[1],
+            board[2],
+            board[3],
+            board[4],
+        ];
+        strengths.push(lookup::evaluate_best(&cards).expect("cards are validated"));
+    }
+
+    let Some(&best) = strengths.iter().max() else {
+        return;
+    };
+    let winners = strengths.iter().filter(|&&s| s == best).count();
+
+    for (result, &strength) in results.iter_mut().zip(strengths.iter()) {
+        if strength != best {
+            result.losses += 1;
+        } else if winners == 1 {
+            result.wins += 1;
+            result.pot_share += 1.0;
+        } else {
+            result.ties += 1;
+            result.pot_share += 1.0 / winners as f64;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use rand::{rngs::StdRng, SeedableRng};
+
+    use super::*;
+
+    fn cards(cards: &[&str]) -> Vec<Card> {
+        cards.iter().map(|c| Card::from_str(c).unwrap()).collect()
+    }
+
+    #[test]
+    fn test_simulate_overpair_is_favourite() {
+        let players = vec![cards(&["ah", "as"]), cards(&["kh", "ks"])];
+        let mut rng = StdRng::seed_from_u64(7);
+
+        let results = simulate(&players, &[], 2_000, &mut rng).unwrap();
+
+        assert_eq!(results[0].boards(), 2_000);
+        assert!(results[0].equity() > 70.0 && results[0].equity() < 92.0);
+        assert!((results[0].equity() + results[1].equity() - 100.0).abs() < 1e-9)
+    }
+
+    #[test]
+    fn test_simulate_is_reproducible() {
+        let players = vec![cards(&["ah", "kh"]), cards(&["7r", "7k"])];
+        let board = cards(&["2h", "9h", "tk"]);
+
+        let first = simulate(&players, &board, 500, &mut StdRng::seed_from_u64(42)).unwrap();
+        let second = simulate(&players, &board, 500, &mut StdRng::seed_from_u64(42)).unwrap();
+
+        assert_eq!(first, second)
+    }
+
+    #[test]
+    fn test_simulate_complete_board() {
+        let players = vec![
+            cards(&["as", "3h"]),
+            cards(&["3r", "4r"]),
+            cards(&["ar", "5k"]),
+        ];
+        let board = cards(&["th", "js", "qr", "kk", "2h"]);
+        let mut rng = StdRng::seed_from_u64(1);
+
+        let results = simulate(&players, &board, 10, &mut rng).unwrap();
+
+        assert_eq!(results[0].tie_percentage(), 100.0);
+        assert_eq!(results[1].loss_percentage(), 100.0);
+        assert_eq!(results[2].equity(), 50.0)
+    }
+
+    #[test]
+    fn test_enumerate_river_outs() {
+        // Nine hearts, three aces and three kings give the first player the best hand
+        let players = vec![cards(&["ah", "kh"]), cards(&["7r", "7k"])];
+        let board = cards(&["2h", "9h", "tk", "3s"]);
+
+        let results = enumerate(&players, &board).unwrap();
+
+        assert_eq!(results[0].wins, 15);
+        assert_eq!(results[0].losses, 29);
+        assert_eq!(results[1].wins, 29);
+        assert_eq!(results[1].ties, 0)
+    }
+
+    #[test]
+    fn test_enumerate_flop() {
+        let players = vec![cards(&["ah", "kh"]), cards(&["7r", "7k"])];
+        let board = cards(&["2h", "9h", "tk"]);
+
+        let exact = enumerate(&players, &board).unwrap();
+
+        assert_eq!(exact[0].boards(), 990);
+        assert_eq!(
+            exact[0].wins + exact[0].ties,
+            exact[1].losses + exact[1].ties
+        )
+    }
+
+    #[test]
+    fn test_simulate_is_close_to_enumerate() {
+        let players = vec![cards(&["ah", "kh"]), cards(&["7r", "7k"])];
+        let board = cards(&["2h", "9h", "tk"]);
+
+        let exact = enumerate(&players, &board).unwrap();
+        let estimate = simulate(&players, &board, 5_000, &mut StdRng::seed_from_u64(3)).unwrap();
+
+        assert!((exact[0].equity() - estimate[0].equity()).abs() < 2.0)
+    }
+
+    #[test]
+    fn test_enumerate_split_board() {
+        let players = vec![cards(&["2s", "3h"]), cards(&["4r", "5r"])];
+        let board = cards(&["ah", "ar", "as", "ak", "kh"]);
+
+        let results = enumerate(&players, &board).unwrap();
+
+        assert_eq!(results[0].ties, 1);
+        assert_eq!(results[1].equity(), 50.0)
+    }
+
+    fn range(text: &str) -> Range {
+        Range::from_str(text).unwrap()
+    }
+
+    #[test]
+    fn test_enumerate_ranges_matches_hands() {
+        let board = cards(&["2h", "9h", "tk", "3s"]);
+
+        let hands = enumerate(&[cards(&["ah", "kh"]), cards(&["7r", "7k"])], &board).unwrap();
+        let ranges = enumerate_ranges(&[range("AhKh"), range("7r7k")], &board).unwrap();
+
+        assert_eq!(ranges[0].wins, hands[0].wins as f64);
+        assert_eq!(ranges[1].losses, hands[1].losses as f64);
+        assert_eq!(ranges[0].equity(), hands[0].equity())
+    }
+
+    #[test]
+    fn test_enumerate_ranges_removes_blockers() {
+        // The board blocks the ace of hearts and the other player the ace of spades, which
+        // leaves only the aces of diamonds and clubs
+        let board = cards(&["ah", "7r", "8k", "2s", "3s"]);
+
+        let results = enumerate_ranges(&[range("AA"), range("AsKs")], &board).unwrap();
+
+        assert_eq!(results[0].total(), 1.0);
+        assert_eq!(results[0].win_percentage(), 100.0)
+    }
+
+    #[test]
+    fn test_enumerate_ranges_weights() {
+        let board = cards(&["2h", "7r", "8k", "9s", "3s"]);
+
+        let results = enumerate_ranges(&[range("JJ:0.5, 22"), range("QhQs")], &board).unwrap();
+
+        // Six combos of jacks at half weight lose and three combos of twos make a set and win
+        assert_eq!(results[0].total(), 6.0);
+        assert_eq!(results[0].wins, 3.0);
+        assert_eq!(results[1].equity(), 50.0)
+    }
+
+    #[test]
+    fn test_simulate_ranges_is_close_to_enumerate_ranges() {
+        let ranges = [range("QQ+, AKs"), range("JJ-99, AQo")];
+        let board = cards(&["2h", "9h", "tk"]);
+        let mut rng = StdRng::seed_from_u64(5);
+
+        let exact = enumerate_ranges(&ranges, &board).unwrap();
+        let estimate = simulate_ranges(&ranges, &board, 5_000, &mut rng).unwrap();
+
+        assert_eq!(estimate[0].total(), 5_000.0);
+        assert!((exact[0].equity() + exact[1].equity() - 100.0).abs() < 1e-9);
+        assert!((exact[0].equity() - estimate[0].equity()).abs() < 2.5)
+    }
+
+    #[test]
+    fn test_range_equity_errors() {
+        let mut rng = StdRng::seed_from_u64(1);
+        let board = cards(&["ah", "7r", "8k"]);
+
+        assert_eq!(
+            enumerate_ranges(&[range("AhAs"), range("AhKh")], &[]).unwrap_err(),
+            HandError::DuplicateCards
+        );
+        assert_eq!(
+            simulate_ranges(&[range("AhKh"), range("QQ")], &board, 10, &mut rng).unwrap_err(),
+            HandError::DuplicateCards
+        );
+        assert_eq!(
+            simulate_ranges(
+                &[range("AA"), range("KK")],
+                &cards(&["ah", "ah"]),
+                10,
+                &mut rng
+            )
+            .unwrap_err(),
+            HandError::DuplicateCards
+        );
+
+        // Combos built by hand with the same card twice or a joker
+        let ace = Card::from_str("ah").unwrap();
+        let pair_of_one_card = Range {
+            combos: vec![Combo {
+                cards: [ace, ace],
+                weight: 1.0,
+            }],
+        };
+        let joker = Range {
+            combos: vec![Combo {
+                cards: [ace, Card::from_str("jo").unwrap()],
+                weight: 1.0,
+            }],
+        };
+        assert_eq!(
+            enumerate_ranges(&[pair_of_one_card, range("KK")], &board).unwrap_err(),
+            HandError::DuplicateCards
+        );
+        assert_eq!(
+            simulate_ranges(&[range("KK"), joker], &[], 10, &mut rng).unwrap_err(),
+            HandError::CardNotInDeck
+        );
+
+        // A range whose combos are never played can't be dealt
+        let mut never = range("KK");
+        for combo in never.combos.iter_mut() {
+            combo.weight = 0.0;
+        }
+        assert_eq!(
+            simulate_ranges(&[range("AA"), never], &[], 10, &mut rng).unwrap_err(),
+            HandError::DuplicateCards
+        )
+    }
+
+    #[test]
+    fn test_simulate_errors() {
+        let mut rng = StdRng::seed_from_u64(1);
+        let duplicate = vec![cards(&["ah", "as"]), cards(&["ah", "ks"])];
+        let one_card = vec![cards(&["ah"]), cards(&["kh", "ks"])];
+        let board = cards(&["2h", "3h", "4h", "5h", "6h", "7h"]);
+
+        assert_eq!(
+            simulate(&duplicate, &[], 10, &mut rng).unwrap_err(),
+            HandError::DuplicateCards
+        );
+        assert_eq!(
+            simulate(&one_card, &[], 10, &mut rng).unwrap_err(),
+            HandError::NotEnoughCards
+        );
+        assert_eq!(
+            simulate(&[], &board, 10, &mut rng).unwrap_err(),
+            HandError::TooManyCards
+        )
+    }
+}
@@ -2,14 +2,17 @@ use std::{collections::HashMap, fmt::Display};
 
 use crate::{
     card::{card::Card, cardvalue::CardValue, suit::Suit},
-    hand_ranks::HandRanking,
+    describe::HandDescription,
+    hand_ranks::{HandRanking, HandValue, Rules},
 };
 
+#[allow(clippy::enum_variant_names)]
 #[derive(Debug, PartialEq, Eq)]
 pub enum HandError {
     NotEnoughCards,
     TooManyCards,
     DuplicateCards,
+    CardNotInDeck,
 }
 
 impl Display for HandError {
@@ -18,6 +21,7 @@ impl Display for HandError {
             HandError::NotEnoughCards => write!(f, "Not enough cards"),
             HandError::TooManyCards => write!(f, "Too many cards"),
             HandError::DuplicateCards => write!(f, "Duplicate cards"),
+            HandError::CardNotInDeck => write!(f, "Card is not in the deck"),
         }
     }
 }
@@ -25,18 +29,49 @@ impl Display for HandError {
 #[derive(Debug, PartialEq, Eq)]
 pub struct Eval {
     hand: Vec<Card>,
+    rules: Rules,
+}
+
+// The best five card hand that can be made from a larger set of cards, like the two hole
+// cards and five board cards in Texas Hold'em, together with the five cards that made it.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct BestHand {
+    pub value: HandValue,
+    pub cards: Vec<Card>,
+}
+
+impl BestHand {
+    pub fn ranking(&self) -> HandRanking {
+        self.value.ranking
+    }
+
+    pub fn describe(&self) -> HandDescription {
+        HandDescription::from(self)
+    }
 }
 
 impl Eval {
     pub fn from(cards: Vec<Card>) -> Eval {
-        Eval { hand: cards }
+        Eval::with_rules(cards, Rules::Standard)
+    }
+
+    pub fn with_rules(cards: Vec<Card>, rules: Rules) -> Eval {
+        Eval { hand: cards, rules }
     }
 
     pub fn evaluate(&self) -> Result<HandRanking, HandError> {
-        if let Err(hand_error) = self.validate_hand() {
-            return Err(hand_error);
-        }
+        Ok(self.evaluate_value()?.ranking)
+    }
 
+    pub fn evaluate_value(&self) -> Result<HandValue, HandError> {
+        self.validate_hand()?;
+
+        Ok(self.value())
+    }
+
+    // Evaluates the five cards without validating them first, which lets wild cards stand in
+    // for a card that is already in the hand
+    pub(crate) fn value(&self) -> HandValue {
         let mut card_values: Vec<CardValue> = self.hand.iter().map(|card| card.value).collect();
         let mut suits: Vec<Suit> = self.hand.iter().map(|card| card.suit).collect();
 
@@ -51,29 +86,48 @@ impl Eval {
         let counts: Vec<i32> = rank_count.values().cloned().collect();
 
         let rankings = self.max_rank(&card_values, &suits, &counts);
+        let ranking = rankings.into_iter().max().unwrap_or(HandRanking::HighCard);
 
-        if let Some(max_ranking) = rankings.iter().max() {
-            Ok(max_ranking.clone())
-        } else {
-            Ok(HandRanking::HighCard)
+        let ranks = tie_break_ranks(&ranking, &rank_count);
+
+        HandValue {
+            ranking,
+            ranks,
+            rules: self.rules,
         }
     }
 
-    fn validate_hand(&self) -> Result<(), HandError> {
-        if self.hand.len() > 5 {
-            return Err(HandError::TooManyCards);
-        }
+    // Evaluates every five card combination of the hand and returns the best one.
+    // Accepts five, six or seven cards.
+    pub fn evaluate_best(&self) -> Result<BestHand, HandError> {
+        validate_cards(&self.hand, 5, 7)?;
+        self.validate_rules()?;
+
+        let mut best_hand: Option<BestHand> = None;
+        for cards in combinations(&self.hand, 5) {
+            let value = Eval::with_rules(cards.clone(), self.rules).evaluate_value()?;
 
-        if self.hand.len() < 5 {
-            return Err(HandError::NotEnoughCards);
+            if best_hand.as_ref().is_none_or(|best| value > best.value) {
+                best_hand = Some(BestHand { value, cards });
+            }
         }
 
-        let mut hand = self.hand.clone();
-        hand.sort();
-        hand.dedup();
+        best_hand.ok_or(HandError::NotEnoughCards)
+    }
+
+    // Describes the best hand out of five to seven cards, with its primary cards and kickers
+    pub fn describe(&self) -> Result<HandDescription, HandError> {
+        Ok(self.evaluate_best()?.describe())
+    }
 
-        if hand.len() != 5 {
-            return Err(HandError::DuplicateCards);
+    fn validate_hand(&self) -> Result<(), HandError> {
+        validate_cards(&self.hand, 5, 5)?;
+        self.validate_rules()
+    }
+
+    fn validate_rules(&self) -> Result<(), HandError> {
+        if !self.hand.iter().all(|card| self.rules.includes(card.value)) {
+            return Err(HandError::CardNotInDeck);
         }
 
         Ok(())
@@ -81,64 +135,64 @@ impl Eval {
 
     fn max_rank(
         &self,
-        card_values: &Vec<CardValue>,
-        suits: &Vec<Suit>,
-        counts: &Vec<i32>,
+        card_values: &[CardValue],
+        suits: &[Suit],
+        counts: &[i32],
     ) -> Vec<HandRanking> {
         let mut rankings: Vec<HandRanking> = Vec::new();
 
         // Pair
         // Pair is a hand that contains two cards of one rank and three cards of three other ranks
         // Example: ["kh", "qh", "5s", "3r", "kr"]
-        if is_pair(&counts) {
+        if is_pair(counts) {
             rankings.push(HandRanking::Pair);
         }
         // Two pair
         // Two pair is a hand that contains two cards of one rank, two cards of another rank and one card of a third rank
         // Example: ["kh", "qh", "qs", "3r", "kr"]
-        if is_two_pair(&counts) {
+        if is_two_pair(counts) {
             rankings.push(HandRanking::TwoPair);
         }
         // Three of a kind
         // Three of a kind is a hand that contains three cards of one rank and two cards of two other ranks
         // Example: ["kh", "ks", "qs", "3r", "kr"]
-        if is_three_of_a_kind(&counts) {
+        if is_three_of_a_kind(counts) {
             rankings.push(HandRanking::ThreeOfAKind);
         }
         // Straight
         // A straight is a hand that contains five cards of sequential rank
         // Example: ["ah", "2s", "3k", "4r", "5r"]
-        if is_straight(&card_values) {
+        if is_straight(card_values, self.rules) {
             rankings.push(HandRanking::Straight)
         };
         // Flush
         // A flush is a hand that contains five cards all of the same suit
         // Example: ["ah", "7h", "qh", "th", "2h"]
-        if is_flush(&suits) {
+        if is_flush(suits) {
             rankings.push(HandRanking::Flush)
         }
         // Full House
         // Full house is a hand that contains three cards of one rank and two cards of another rank
         // Example: ["ah", "ar", "qh", "qr", "qs"]
-        if is_pair(&counts) && is_three_of_a_kind(&counts) {
+        if is_pair(counts) && is_three_of_a_kind(counts) {
             rankings.push(HandRanking::FullHouse)
         }
         // Four of kind
         // Four of a kind, also known as quads, is a hand that contains four cards of one rank and one card of another rank
         // Example: ["ah", "ar", "as", "ak", "qs"]
-        if is_four_of_a_kind(&counts) {
+        if is_four_of_a_kind(counts) {
             rankings.push(HandRanking::FourOfAKind);
         }
         // Straight flush
         // A straight flush is a hand that contains five cards of sequential rank, all of the same suit
         // Example: ["ah", "2h", "3h", "4h", "5h"]
-        if is_straight(&card_values) && is_flush(&suits) {
+        if is_straight(card_values, self.rules) && is_flush(suits) {
             rankings.push(HandRanking::StraightFlush);
         }
         // Royal straight flush
         // Royal straight flush is a hand with an ace-high straight flush from 10 to ace
         // Example: ["th", "jh", "qh", "kh", "ah"]
-        if is_royal_straight_flush(&card_values, &suits) {
+        if is_royal_straight_flush(card_values, suits, self.rules) {
             rankings.push(HandRanking::RoyalStraightFlush)
         }
 
@@ -146,51 +200,126 @@ impl Eval {
     }
 }
 
-fn is_pair(count: &Vec<i32>) -> bool {
+pub(crate) fn validate_cards(cards: &[Card], min: usize, max: usize) -> Result<(), HandError> {
+    if cards.len() > max {
+        return Err(HandError::TooManyCards);
+    }
+
+    if cards.len() < min {
+        return Err(HandError::NotEnoughCards);
+    }
+
+    // Jokers can only be used when evaluating with wild cards
+    if cards.iter().any(|card| card.is_joker()) {
+        return Err(HandError::CardNotInDeck);
+    }
+
+    let mut unique = cards.to_vec();
+    unique.sort();
+    unique.dedup();
+
+    if unique.len() != cards.len() {
+        return Err(HandError::DuplicateCards);
+    }
+
+    Ok(())
+}
+
+// Returns every combination of `k` cards, keeping the order the cards were given in
+pub(crate) fn combinations(cards: &[Card], k: usize) -> Vec<Vec<Card>> {
+    if k == 0 {
+        return vec![Vec::new()];
+    }
+
+    let mut result = Vec::new();
+    for (i, &card) in cards.iter().enumerate() {
+        for mut rest in combinations(&cards[i + 1..], k - 1) {
+            rest.insert(0, card);
+            result.push(rest);
+        }
+    }
+
+    result
+}
+
+fn is_pair(count: &[i32]) -> bool {
     count.contains(&2)
 }
 
-fn is_three_of_a_kind(counts: &Vec<i32>) -> bool {
+fn is_three_of_a_kind(counts: &[i32]) -> bool {
     counts.contains(&3)
 }
 
-fn is_four_of_a_kind(counts: &Vec<i32>) -> bool {
+fn is_four_of_a_kind(counts: &[i32]) -> bool {
     counts.contains(&4)
 }
 
-fn is_two_pair(counts: &Vec<i32>) -> bool {
+fn is_two_pair(counts: &[i32]) -> bool {
     counts.iter().filter(|&&count| count == 2).count() == 2
 }
 
-fn is_straight(card_ranks: &Vec<CardValue>) -> bool {
+fn is_straight(card_ranks: &[CardValue], rules: Rules) -> bool {
     // Since `card_ranks` is sorted, we can check that the next card is greater than the previous card.
     // If this is true for all elements, it's a straight.
     //
-    // We also check if `card_ranks` is equal to [2,3,4,5,a] to handle the edge case where ace can be 1
+    // We also check if `card_ranks` is equal to [2,3,4,5,a] to handle the edge case where ace can be 1.
+    // In short-deck the ace goes below the six instead, so [6,7,8,9,a] is the lowest straight.
+    let low_straight = match rules {
+        Rules::Standard => [
+            CardValue::Two,
+            CardValue::Three,
+            CardValue::Four,
+            CardValue::Five,
+            CardValue::A,
+        ],
+        Rules::ShortDeck => [
+            CardValue::Six,
+            CardValue::Seven,
+            CardValue::Eigth,
+            CardValue::Nine,
+            CardValue::A,
+        ],
+    };
+
     card_ranks
         .windows(2)
         .all(|c| c[0] as usize + 1 == c[1] as usize)
-        || *card_ranks
-            == vec![
-                CardValue::Two,
-                CardValue::Three,
-                CardValue::Four,
-                CardValue::Five,
-                CardValue::A,
-            ]
+        || *card_ranks == low_straight
 }
 
-fn is_flush(suits: &Vec<Suit>) -> bool {
+fn is_flush(suits: &[Suit]) -> bool {
     suits.windows(2).all(|s| s[0] == s[1])
 }
 
-fn is_royal_straight_flush(card_ranks: &Vec<CardValue>, suits: &Vec<Suit>) -> bool {
+fn is_royal_straight_flush(card_ranks: &[CardValue], suits: &[Suit], rules: Rules) -> bool {
     // We check if it is a straight, and the value of the cards sum up to 50(10 + 11 + 12 + 13 + 14)
     // which means it has to be a royal straight
-    let is_royal_straight = is_straight(&card_ranks)
-        && card_ranks.into_iter().map(|cr| *cr as usize).sum::<usize>() == 60;
+    let is_royal_straight = is_straight(card_ranks, rules)
+        && card_ranks.iter().map(|cr| *cr as usize).sum::<usize>() == 60;
+
+    is_royal_straight && is_flush(suits)
+}
+
+fn tie_break_ranks(ranking: &HandRanking, rank_count: &HashMap<CardValue, i32>) -> Vec<CardValue> {
+    // Ranks are ordered by how many times they appear in the hand and then by the rank itself,
+    // so for a full house the three of a kind comes before the pair, and kickers come last.
+    let mut groups: Vec<(i32, CardValue)> = rank_count
+        .iter()
+        .map(|(&rank, &count)| (count, rank))
+        .collect();
+    groups.sort_by(|a, b| b.cmp(a));
+
+    let mut ranks: Vec<CardValue> = groups.into_iter().map(|(_, rank)| rank).collect();
+
+    // In a straight from ace to five the ace counts as one, so it is moved last
+    // to make the five-high straight lose against a six-high straight.
+    // The same goes for the ace to nine straight in short-deck.
+    let is_straight_ranking = matches!(ranking, HandRanking::Straight | HandRanking::StraightFlush);
+    if is_straight_ranking && ranks[0] == CardValue::A && ranks[1] != CardValue::K {
+        ranks.rotate_left(1);
+    }
 
-    is_royal_straight && is_flush(&suits)
+    ranks
 }
 
 #[cfg(test)]
@@ -362,4 +491,239 @@ mod tests {
 
         assert_eq!(evaluation, HandRanking::RoyalStraightFlush)
     }
+
+    #[test]
+    fn test_hand_value_kickers() {
+        let kings_ace_kicker: Vec<Card> = ["kr", "ks", "ah", "7s", "3h"]
+            .map(|c| Card::from_str(c).unwrap())
+            .to_vec();
+        let kings_queen_kicker: Vec<Card> = ["kh", "kk", "qh", "7r", "3s"]
+            .map(|c| Card::from_str(c).unwrap())
+            .to_vec();
+
+        let value_one = Eval::from(kings_ace_kicker).evaluate_value().unwrap();
+        let value_two = Eval::from(kings_queen_kicker).evaluate_value().unwrap();
+
+        assert_eq!(
+            value_one,
+            HandValue {
+                ranking: HandRanking::Pair,
+                ranks: vec![
+                    CardValue::K,
+                    CardValue::A,
+                    CardValue::Seven,
+                    CardValue::Three
+                ],
+                rules: Rules::Standard,
+            }
+        );
+        assert!(value_one > value_two)
+    }
+
+    #[test]
+    fn test_hand_value_flush_high_card() {
+        let ace_high: Vec<Card> = ["ah", "7h", "qh", "th", "2h"]
+            .map(|c| Card::from_str(c).unwrap())
+            .to_vec();
+        let nine_high: Vec<Card> = ["9s", "7s", "5s", "4s", "2s"]
+            .map(|c| Card::from_str(c).unwrap())
+            .to_vec();
+
+        let value_one = Eval::from(ace_high).evaluate_value().unwrap();
+        let value_two = Eval::from(nine_high).evaluate_value().unwrap();
+
+        assert!(value_one > value_two)
+    }
+
+    #[test]
+    fn test_hand_value_full_house_trips_first() {
+        let nines_full: Vec<Card> = ["ts", "tr", "9s", "9r", "9h"]
+            .map(|c| Card::from_str(c).unwrap())
+            .to_vec();
+        let eights_full: Vec<Card> = ["as", "ar", "8s", "8r", "8h"]
+            .map(|c| Card::from_str(c).unwrap())
+            .to_vec();
+
+        let value_one = Eval::from(nines_full).evaluate_value().unwrap();
+        let value_two = Eval::from(eights_full).evaluate_value().unwrap();
+
+        assert_eq!(value_one.ranks, vec![CardValue::Nine, CardValue::T]);
+        assert!(value_one > value_two)
+    }
+
+    #[test]
+    fn test_hand_value_wheel_is_lowest_straight() {
+        let wheel: Vec<Card> = ["as", "5h", "3h", "4r", "2r"]
+            .map(|c| Card::from_str(c).unwrap())
+            .to_vec();
+        let six_high: Vec<Card> = ["6s", "5s", "3h", "4r", "2r"]
+            .map(|c| Card::from_str(c).unwrap())
+            .to_vec();
+
+        let value_one = Eval::from(wheel).evaluate_value().unwrap();
+        let value_two = Eval::from(six_high).evaluate_value().unwrap();
+
+        assert_eq!(value_one.ranking, HandRanking::Straight);
+        assert!(value_one < value_two)
+    }
+
+    #[test]
+    fn test_hand_value_split() {
+        let cards_one: Vec<Card> = ["kr", "ks", "jh", "js", "3h"]
+            .map(|c| Card::from_str(c).unwrap())
+            .to_vec();
+        let cards_two: Vec<Card> = ["kh", "kk", "jr", "jk", "3s"]
+            .map(|c| Card::from_str(c).unwrap())
+            .to_vec();
+
+        let value_one = Eval::from(cards_one).evaluate_value().unwrap();
+        let value_two = Eval::from(cards_two).evaluate_value().unwrap();
+
+        assert_eq!(value_one, value_two)
+    }
+
+    #[test]
+    fn test_best_hand_seven_cards() {
+        let cards: Vec<Card> = ["kr", "ks", "jh", "js", "3h", "9h", "jr"]
+            .map(|c| Card::from_str(c).unwrap())
+            .to_vec();
+
+        let eval = Eval::from(cards);
+        let best_hand = eval.evaluate_best().unwrap();
+
+        let mut best_cards = best_hand.cards.clone();
+        best_cards.sort();
+        let mut expected: Vec<Card> = ["kr", "ks", "jh", "js", "jr"]
+            .map(|c| Card::from_str(c).unwrap())
+            .to_vec();
+        expected.sort();
+
+        assert_eq!(best_hand.ranking(), HandRanking::FullHouse);
+        assert_eq!(best_cards, expected)
+    }
+
+    #[test]
+    fn test_best_hand_six_cards() {
+        let cards: Vec<Card> = ["2h", "7h", "qh", "th", "kr", "4h"]
+            .map(|c| Card::from_str(c).unwrap())
+            .to_vec();
+
+        let eval = Eval::from(cards);
+        let best_hand = eval.evaluate_best().unwrap();
+
+        assert_eq!(best_hand.ranking(), HandRanking::Flush);
+        assert!(!best_hand.cards.contains(&Card::from_str("kr").unwrap()))
+    }
+
+    #[test]
+    fn test_best_hand_picks_highest_straight() {
+        let cards: Vec<Card> = ["4s", "5h", "6r", "7k", "8h", "9s", "2h"]
+            .map(|c| Card::from_str(c).unwrap())
+            .to_vec();
+
+        let eval = Eval::from(cards);
+        let best_hand = eval.evaluate_best().unwrap();
+
+        assert_eq!(best_hand.ranking(), HandRanking::Straight);
+        assert_eq!(best_hand.value.ranks[0], CardValue::Nine)
+    }
+
+    #[test]
+    fn test_best_hand_errors() {
+        let too_many: Vec<Card> = ["4s", "5h", "6r", "7k", "8h", "9s", "2h", "ah"]
+            .map(|c| Card::from_str(c).unwrap())
+            .to_vec();
+        let duplicates: Vec<Card> = ["4s", "5h", "6r", "7k", "8h", "4s"]
+            .map(|c| Card::from_str(c).unwrap())
+            .to_vec();
+
+        assert_eq!(
+            Eval::from(too_many).evaluate_best().unwrap_err(),
+            HandError::TooManyCards
+        );
+        assert_eq!(
+            Eval::from(duplicates).evaluate_best().unwrap_err(),
+            HandError::DuplicateCards
+        )
+    }
+
+    #[test]
+    fn test_short_deck_flush_beats_full_house() {
+        let flush: Vec<Card> = ["6h", "8h", "th", "qh", "ah"]
+            .map(|c| Card::from_str(c).unwrap())
+            .to_vec();
+        let full_house: Vec<Card> = ["as", "ar", "ak", "ks", "kr"]
+            .map(|c| Card::from_str(c).unwrap())
+            .to_vec();
+
+        let short_flush = Eval::with_rules(flush.clone(), Rules::ShortDeck)
+            .evaluate_value()
+            .unwrap();
+        let short_full_house = Eval::with_rules(full_house.clone(), Rules::ShortDeck)
+            .evaluate_value()
+            .unwrap();
+        let standard_flush = Eval::from(flush).evaluate_value().unwrap();
+        let standard_full_house = Eval::from(full_house).evaluate_value().unwrap();
+
+        assert!(short_flush > short_full_house);
+        assert!(standard_flush < standard_full_house)
+    }
+
+    #[test]
+    fn test_short_deck_ace_to_nine_straight() {
+        let low_straight: Vec<Card> = ["as", "6h", "7h", "8r", "9k"]
+            .map(|c| Card::from_str(c).unwrap())
+            .to_vec();
+        let ten_high: Vec<Card> = ["th", "6s", "7r", "8k", "9h"]
+            .map(|c| Card::from_str(c).unwrap())
+            .to_vec();
+
+        let short_low = Eval::with_rules(low_straight.clone(), Rules::ShortDeck)
+            .evaluate_value()
+            .unwrap();
+        let short_ten_high = Eval::with_rules(ten_high, Rules::ShortDeck)
+            .evaluate_value()
+            .unwrap();
+        let standard = Eval::from(low_straight).evaluate().unwrap();
+
+        assert_eq!(short_low.ranking, HandRanking::Straight);
+        assert!(short_low < short_ten_high);
+        assert_eq!(standard, HandRanking::HighCard)
+    }
+
+    #[test]
+    fn test_short_deck_best_hand() {
+        let cards: Vec<Card> = ["as", "6s", "7s", "8s", "9s", "tr", "ar"]
+            .map(|c| Card::from_str(c).unwrap())
+            .to_vec();
+
+        let best_hand = Eval::with_rules(cards, Rules::ShortDeck)
+            .evaluate_best()
+            .unwrap();
+
+        assert_eq!(best_hand.ranking(), HandRanking::StraightFlush);
+        assert_eq!(best_hand.value.ranks[0], CardValue::Nine)
+    }
+
+    #[test]
+    fn test_joker_is_not_in_the_deck() {
+        let cards: Vec<Card> = ["jo", "ks", "qs", "js", "ts"]
+            .map(|c| Card::from_str(c).unwrap())
+            .to_vec();
+
+        let eval = Eval::from(cards);
+
+        assert_eq!(eval.evaluate().unwrap_err(), HandError::CardNotInDeck)
+    }
+
+    #[test]
+    fn test_short_deck_rejects_low_cards() {
+        let cards: Vec<Card> = ["as", "6s", "7s", "8s", "5s"]
+            .map(|c| Card::from_str(c).unwrap())
+            .to_vec();
+
+        let eval = Eval::with_rules(cards, Rules::ShortDeck);
+
+        assert_eq!(eval.evaluate().unwrap_err(), HandError::CardNotInDeck)
+    }
 }
@@ -0,0 +1,328 @@
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+use super::hand::{Action, Street};
+
+// In fixed-limit a street is capped after a bet and three raises
+pub const FIXED_LIMIT_BETS_PER_STREET: u32 = 4;
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum BettingError {
+    CannotCheck,
+    NothingToCall,
+    CannotBet,
+    NothingToRaise,
+    BetTooSmall,
+    BetTooLarge,
+    NotEnoughChips,
+    RaisingClosed,
+    RaisesCapped,
+}
+
+impl fmt::Display for BettingError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BettingError::CannotCheck => write!(f, "Cannot check when there is a bet to call"),
+            BettingError::NothingToCall => write!(f, "There is no bet to call"),
+            BettingError::CannotBet => {
+                write!(f, "Cannot bet when there is already a bet, raise instead")
+            }
+            BettingError::NothingToRaise => write!(f, "There is no bet to raise, bet instead"),
+            BettingError::BetTooSmall => write!(f, "Bet or raise is less than the minimum"),
+            BettingError::BetTooLarge => write!(f, "Bet or raise is more than the maximum"),
+            BettingError::NotEnoughChips => write!(f, "Not enough chips for the bet or raise"),
+            BettingError::RaisingClosed => {
+                write!(
+                    f,
+                    "Cannot raise again after an all-in that was less than a full raise"
+                )
+            }
+            BettingError::RaisesCapped => write!(f, "No more raises are allowed on this street"),
+        }
+    }
+}
+
+// How much a player may bet or raise. In fixed-limit every bet and raise is one small bet, the
+// size of the big blind, before the flop and on the flop, and one big bet, twice the big blind,
+// on the turn and the river.
+#[derive(
+    Serialize, Deserialize, Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Hash, Default,
+)]
+pub enum BettingStructure {
+    #[default]
+    NoLimit,
+    PotLimit,
+    FixedLimit,
+}
+
+// The betting round as the player whose turn it is sees it. Bets are the total put in on the
+// current street, like in `Action`.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct BettingState {
+    pub street: Street,
+    pub big_blind: u64,
+    // Every chip put in over the hand, including the bets on the current street
+    pub pot: u64,
+    // The highest bet on the street
+    pub current_bet: u64,
+    // The size of the last full bet or raise
+    pub min_raise: u64,
+    // The number of bets and raises on the street, the big blind counts as the first bet
+    pub raises: u32,
+    // What the player has put in on the street and has left to bet with
+    pub bet: u64,
+    pub stack: u64,
+    // False when the action only came back to the player because of an all-in that was less
+    // than a full raise, then they may only call or fold
+    pub can_raise: bool,
+}
+
+impl BettingState {
+    pub fn to_call(&self) -> u64 {
+        (self.current_bet - self.bet).min(self.stack)
+    }
+
+    fn all_in(&self) -> u64 {
+        self.bet + self.stack
+    }
+}
+
+impl BettingStructure {
+    // The smallest and the largest total the player can bet or raise to. When the player has
+    // less than the minimum, going all-in is the only raise. Fails when the player can't bet or
+    // raise at all.
+    pub fn raise_range(&self, state: &BettingState) -> Result<(u64, u64), BettingError> {
+        if !state.can_raise {
+            return Err(BettingError::RaisingClosed);
+        }
+
+        if *self == BettingStructure::FixedLimit && state.raises >= FIXED_LIMIT_BETS_PER_STREET {
+            return Err(BettingError::RaisesCapped);
+        }
+
+        if state.all_in() <= state.current_bet {
+            return Err(BettingError::NotEnoughChips);
+        }
+
+        let (min, max) = match self {
+            BettingStructure::NoLimit => (state.current_bet + state.min_raise, state.all_in()),
+            BettingStructure::PotLimit => {
+                // A pot sized raise first calls and then raises by the size of the pot. The pot can
+                // be smaller than a full raise when the big blind was posted short, then the
+                // minimum raise is still allowed.
+                let call = state.current_bet - state.bet;
+                let min = state.current_bet + state.min_raise;
+                (min, (state.current_bet + state.pot + call).max(min))
+            }
+            BettingStructure::FixedLimit => {
+                let size = match state.street {
+                    Street::Preflop | Street::Flop => state.big_blind,
+                    Street::Turn | Street::River => 2 * state.big_blind,
+                };
+                (state.current_bet + size, state.current_bet + size)
+            }
+        };
+
+        Ok((min.min(state.all_in()), max.min(state.all_in())))
+    }
+
+    // Checks that the action is allowed and returns the total the player has bet on the street
+    // after it. Folding is always allowed.
+    pub fn validate(&self, state: &BettingState, action: Action) -> Result<u64, BettingError> {
+        match action {
+            Action::Fold => Ok(state.bet),
+            Action::Check if state.current_bet > state.bet => Err(BettingError::CannotCheck),
+            Action::Check => Ok(state.bet),
+            Action::Call if state.current_bet == state.bet => Err(BettingError::NothingToCall),
+            Action::Call => Ok(state.current_bet.min(state.all_in())),
+            Action::Bet(_) if state.current_bet > 0 => Err(BettingError::CannotBet),
+            Action::Raise(_) if state.current_bet == 0 => Err(BettingError::NothingToRaise),
+            Action::Bet(amount) | Action::Raise(amount) => self.validate_raise(state, amount),
+            Action::AllIn if state.all_in() <= state.current_bet => Ok(state.all_in()),
+            Action::AllIn => self.validate_raise(state, state.all_in()),
+        }
+    }
+
+    fn validate_raise(&self, state: &BettingState, amount: u64) -> Result<u64, BettingError> {
+        if amount > state.all_in() {
+            return Err(BettingError::NotEnoughChips);
+        }
+
+        let (min, max) = self.raise_range(state)?;
+        if amount < min {
+            return Err(BettingError::BetTooSmall);
+        }
+        if amount > max {
+            return Err(BettingError::BetTooLarge);
+        }
+
+        Ok(amount)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Before the flop with blinds of 1 and 2, the first player to act with 100 chips
+    fn preflop() -> BettingState {
+        BettingState {
+            street: Street::Preflop,
+            big_blind: 2,
+            pot: 3,
+            current_bet: 2,
+            min_raise: 2,
+            raises: 1,
+            bet: 0,
+            stack: 100,
+            can_raise: true,
+        }
+    }
+
+    // On the turn with 20 chips in the pot and no bet yet
+    fn turn() -> BettingState {
+        BettingState {
+            street: Street::Turn,
+            pot: 20,
+            current_bet: 0,
+            raises: 0,
+            ..preflop()
+        }
+    }
+
+    #[test]
+    fn test_no_limit_raise_range() {
+        let structure = BettingStructure::NoLimit;
+
+        assert_eq!(structure.raise_range(&preflop()), Ok((4, 100)));
+        assert_eq!(structure.raise_range(&turn()), Ok((2, 100)));
+        assert_eq!(structure.validate(&preflop(), Action::Raise(100)), Ok(100));
+        assert_eq!(
+            structure.validate(&preflop(), Action::Raise(3)),
+            Err(BettingError::BetTooSmall)
+        )
+    }
+
+    #[test]
+    fn test_pot_limit_raise_range() {
+        let structure = BettingStructure::PotLimit;
+
+        // Calling 2 makes the pot 5, so the biggest raise is to 7
+        assert_eq!(structure.raise_range(&preflop()), Ok((4, 7)));
+        assert_eq!(structure.raise_range(&turn()), Ok((2, 20)));
+        assert_eq!(structure.validate(&turn(), Action::Bet(20)), Ok(20));
+        assert_eq!(
+            structure.validate(&turn(), Action::Bet(21)),
+            Err(BettingError::BetTooLarge)
+        );
+        assert_eq!(
+            structure.validate(&preflop(), Action::AllIn),
+            Err(BettingError::BetTooLarge)
+        )
+    }
+
+    #[test]
+    fn test_pot_limit_with_a_short_big_blind() {
+        // Blinds of 1 and 6, with the big blind all-in for 2
+        let short_blind = BettingState {
+            big_blind: 6,
+            min_raise: 6,
+            stack: 12,
+            ..preflop()
+        };
+        let structure = BettingStructure::PotLimit;
+
+        assert_eq!(structure.raise_range(&short_blind), Ok((8, 8)));
+        assert_eq!(structure.validate(&short_blind, Action::Raise(8)), Ok(8))
+    }
+
+    #[test]
+    fn test_fixed_limit() {
+        let structure = BettingStructure::FixedLimit;
+
+        assert_eq!(structure.raise_range(&preflop()), Ok((4, 4)));
+        assert_eq!(structure.raise_range(&turn()), Ok((4, 4)));
+        assert_eq!(
+            structure.validate(&turn(), Action::Bet(2)),
+            Err(BettingError::BetTooSmall)
+        );
+
+        let capped = BettingState {
+            current_bet: 8,
+            raises: 4,
+            ..preflop()
+        };
+        assert_eq!(
+            structure.validate(&capped, Action::Raise(10)),
+            Err(BettingError::RaisesCapped)
+        );
+        assert_eq!(structure.validate(&capped, Action::Call), Ok(8))
+    }
+
+    #[test]
+    fn test_short_stack_can_only_go_all_in() {
+        let short = BettingState {
+            stack: 3,
+            ..preflop()
+        };
+
+        assert_eq!(BettingStructure::NoLimit.raise_range(&short), Ok((3, 3)));
+        assert_eq!(
+            BettingStructure::FixedLimit.validate(&short, Action::AllIn),
+            Ok(3)
+        );
+
+        let shorter = BettingState {
+            stack: 1,
+            ..preflop()
+        };
+        assert_eq!(
+            BettingStructure::NoLimit.raise_range(&shorter),
+            Err(BettingError::NotEnoughChips)
+        );
+        assert_eq!(
+            BettingStructure::NoLimit.validate(&shorter, Action::AllIn),
+            Ok(1)
+        );
+        assert_eq!(
+            BettingStructure::NoLimit.validate(&shorter, Action::Call),
+            Ok(1)
+        )
+    }
+
+    #[test]
+    fn test_betting_errors() {
+        let structure = BettingStructure::NoLimit;
+        let closed = BettingState {
+            can_raise: false,
+            ..preflop()
+        };
+
+        assert_eq!(
+            structure.validate(&preflop(), Action::Check),
+            Err(BettingError::CannotCheck)
+        );
+        assert_eq!(
+            structure.validate(&turn(), Action::Call),
+            Err(BettingError::NothingToCall)
+        );
+        assert_eq!(
+            structure.validate(&preflop(), Action::Bet(10)),
+            Err(BettingError::CannotBet)
+        );
+        assert_eq!(
+            structure.validate(&turn(), Action::Raise(10)),
+            Err(BettingError::NothingToRaise)
+        );
+        assert_eq!(
+            structure.validate(&preflop(), Action::Raise(101)),
+            Err(BettingError::NotEnoughChips)
+        );
+        assert_eq!(
+            structure.validate(&closed, Action::Raise(10)),
+            Err(BettingError::RaisingClosed)
+        );
+        assert_eq!(structure.validate(&closed, Action::Fold), Ok(0))
+    }
+}
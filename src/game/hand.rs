@@ -0,0 +1,734 @@
+use std::fmt;
+
+use crate::{
+    card::{card::Card, deck::Deck},
+    eval::{validate_cards, BestHand, Eval, HandError},
+    hand_ranks::HandValue,
+};
+
+use super::{
+    betting::{BettingError, BettingState, BettingStructure},
+    pot::{self, Pot, Settlement},
+};
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum GameError {
+    NotEnoughPlayers,
+    NotEnoughCards,
+    HandIsOver,
+    HandNotOver,
+    IllegalAction(BettingError),
+    // The deck dealt a joker or the same card twice
+    InvalidCards(HandError),
+    InvalidBlinds,
+}
+
+impl fmt::Display for GameError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GameError::NotEnoughPlayers => write!(f, "At least two players with chips are needed"),
+            GameError::NotEnoughCards => write!(f, "Not enough cards left in the deck"),
+            GameError::HandIsOver => write!(f, "The hand is over"),
+            GameError::HandNotOver => write!(f, "The hand is not over yet"),
+            GameError::IllegalAction(error) => write!(f, "{}", error),
+            GameError::InvalidCards(error) => write!(f, "Invalid cards dealt: {}", error),
+            GameError::InvalidBlinds => write!(f, "Both blinds must be more than 0"),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Hash)]
+pub enum Street {
+    Preflop,
+    Flop,
+    Turn,
+    River,
+}
+
+impl Street {
+    pub fn next(&self) -> Option<Street> {
+        match self {
+            Street::Preflop => Some(Street::Flop),
+            Street::Flop => Some(Street::Turn),
+            Street::Turn => Some(Street::River),
+            Street::River => None,
+        }
+    }
+}
+
+// Bets and raises are the total the player has put in on the street after the action, so when
+// the big blind is 2 a raise to 6 puts in 4 more chips from the big blind
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+pub enum Action {
+    Fold,
+    Check,
+    Call,
+    Bet(u64),
+    Raise(u64),
+    AllIn,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Default)]
+pub struct Seat {
+    // The chips the player has left to bet with
+    pub stack: u64,
+    // Empty when the player sits out the hand because they have no chips
+    pub hole_cards: Vec<Card>,
+    // What the player has put in on the current street
+    pub bet: u64,
+    // What the player has put in over the whole hand
+    pub contributed: u64,
+    pub folded: bool,
+}
+
+impl Seat {
+    // The player can still win the pot
+    pub fn in_hand(&self) -> bool {
+        !self.hole_cards.is_empty() && !self.folded
+    }
+
+    pub fn is_all_in(&self) -> bool {
+        self.in_hand() && self.stack == 0
+    }
+
+    fn can_act(&self) -> bool {
+        self.in_hand() && self.stack > 0
+    }
+
+    fn put_in(&mut self, amount: u64) {
+        let amount = amount.min(self.stack);
+
+        self.stack -= amount;
+        self.bet += amount;
+        self.contributed += amount;
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct HandResult {
+    pub pots: Vec<Pot>,
+    // What each seat won from the pot, together with any bet that nobody called
+    pub payouts: Vec<u64>,
+    // How much the stack of each seat went up or down over the hand
+    pub stack_changes: Vec<i64>,
+    // The best hand of each seat that went to showdown, `None` for the others and for everyone
+    // when the hand ended with a fold
+    pub hands: Vec<Option<BestHand>>,
+}
+
+// A single hand of Texas Hold'em. The blinds are posted and the hole cards dealt when
+// the hand is created, after that the seat in `to_act` gives one action at a time with `act`
+// until the hand is over and `result` is set.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Hand {
+    pub seats: Vec<Seat>,
+    pub board: Vec<Card>,
+    pub street: Street,
+    pub button: usize,
+    pub big_blind: u64,
+    pub structure: BettingStructure,
+    // The seat whose turn it is, `None` once the hand is over
+    pub to_act: Option<usize>,
+    // The highest bet on the current street
+    pub current_bet: u64,
+    // The size of the last full bet or raise, a raise has to be at least this much more than
+    // the current bet unless the player goes all-in
+    pub min_raise: u64,
+    pub result: Option<HandResult>,
+    // The number of full bets and raises on the current street, the big blind counts as one
+    raises: u32,
+    // Seats that have acted since the last full bet or raise, they may only call or fold when
+    // an all-in for less than a full raise comes back to them
+    acted: Vec<bool>,
+    starting_stacks: Vec<u64>,
+    deck: Deck,
+}
+
+impl Hand {
+    // Seats with no chips sit out the hand. If the button is on one of them, it goes to the
+    // closest seat with chips before it.
+    pub fn new(
+        stacks: &[u64],
+        button: usize,
+        small_blind: u64,
+        big_blind: u64,
+        deck: Deck,
+    ) -> Result<Hand, GameError> {
+        Hand::with_structure(
+            stacks,
+            button,
+            small_blind,
+            big_blind,
+            BettingStructure::NoLimit,
+            deck,
+        )
+    }
+
+    pub fn with_structure(
+        stacks: &[u64],
+        button: usize,
+        small_blind: u64,
+        big_blind: u64,
+        structure: BettingStructure,
+        deck: Deck,
+    ) -> Result<Hand, GameError> {
+        let n = stacks.len();
+        let mut deck = deck;
+        let mut seats: Vec<Seat> = stacks
+            .iter()
+            .map(|&stack| Seat {
+                stack,
+                ..Seat::default()
+            })
+            .collect();
+
+        // The seats with chips from the left of the button around to the button
+        let active: Vec<usize> = (1..=n)
+            .map(|i| (button + i) % n)
+            .filter(|&seat| stacks[seat] > 0)
+            .collect();
+        if active.len() < 2 {
+            return Err(GameError::NotEnoughPlayers);
+        }
+
+        // Heads-up the button posts the small blind and acts first before the flop
+        let (small, big) = match active.as_slice() {
+            [big, small] => (*small, *big),
+            [small, big, ..] => (*small, *big),
+            _ => return Err(GameError::NotEnoughPlayers),
+        };
+
+        for _ in 0..2 {
+            for &seat in &active {
+                let card = deck.deal(1).map_err(|_| GameError::NotEnoughCards)?;
+                seats[seat].hole_cards.extend(card);
+            }
+        }
+        check_dealt(&seats, &[])?;
+
+        seats[small].put_in(small_blind);
+        seats[big].put_in(big_blind);
+
+        let mut hand = Hand {
+            current_bet: seats[small].bet.max(seats[big].bet),
+            seats,
+            board: Vec::new(),
+            street: Street::Preflop,
+            button: active[active.len() - 1],
+            big_blind,
+            structure,
+            to_act: None,
+            min_raise: big_blind,
+            result: None,
+            raises: 1,
+            acted: vec![false; n],
+            starting_stacks: stacks.to_vec(),
+            deck,
+        };
+        hand.advance(big)?;
+
+        Ok(hand)
+    }
+
+    pub fn is_over(&self) -> bool {
+        self.result.is_some()
+    }
+
+    pub fn pot(&self) -> u64 {
+        self.seats.iter().map(|seat| seat.contributed).sum()
+    }
+
+    // What the seat whose turn it is has to put in to call
+    pub fn to_call(&self) -> u64 {
+        self.to_act.map_or(0, |seat| {
+            (self.current_bet - self.seats[seat].bet).min(self.seats[seat].stack)
+        })
+    }
+
+    // The betting round as the seat whose turn it is sees it, `None` once the hand is over
+    pub fn betting_state(&self) -> Option<BettingState> {
+        let seat = self.to_act?;
+
+        Some(BettingState {
+            street: self.street,
+            big_blind: self.big_blind,
+            pot: self.pot(),
+            current_bet: self.current_bet,
+            min_raise: self.min_raise,
+            raises: self.raises,
+            bet: self.seats[seat].bet,
+            stack: self.seats[seat].stack,
+            can_raise: !self.acted[seat],
+        })
+    }
+
+    pub fn act(&mut self, action: Action) -> Result<(), GameError> {
+        let (Some(seat), Some(state)) = (self.to_act, self.betting_state()) else {
+            return Err(GameError::HandIsOver);
+        };
+
+        let target = self
+            .structure
+            .validate(&state, action)
+            .map_err(GameError::IllegalAction)?;
+
+        match action {
+            Action::Fold => self.seats[seat].folded = true,
+            _ => self.bet_to(seat, target),
+        }
+        self.acted[seat] = true;
+
+        // A deck that runs out or deals bad cards ends the hand without a result, nobody can act
+        // on a board that can't be dealt
+        let advanced = self.advance(seat);
+        if advanced.is_err() {
+            self.to_act = None;
+        }
+        advanced
+    }
+
+    fn bet_to(&mut self, seat: usize, target: u64) {
+        if target > self.current_bet {
+            let raise = target - self.current_bet;
+
+            // Only a full raise lets the players who already acted raise again
+            if raise >= self.min_raise {
+                self.min_raise = raise;
+                self.raises += 1;
+                self.acted.fill(false);
+            }
+            self.current_bet = target;
+        }
+
+        let amount = target - self.seats[seat].bet;
+        self.seats[seat].put_in(amount);
+    }
+
+    // Moves the action on after `from` acted, dealing the next streets when the betting round
+    // is over and going to showdown after the river
+    fn advance(&mut self, from: usize) -> Result<(), GameError> {
+        if self.seats.iter().filter(|seat| seat.in_hand()).count() == 1 {
+            self.finish(vec![None; self.seats.len()]);
+            return Ok(());
+        }
+
+        if let Some(seat) = self.next_to_act(from) {
+            self.to_act = Some(seat);
+            return Ok(());
+        }
+
+        loop {
+            let Some(street) = self.street.next() else {
+                self.reset_bets();
+                return self.showdown();
+            };
+
+            // The cards are checked before anything else about the hand changes
+            let cards = self.deal_street(street)?;
+            self.reset_bets();
+            self.board.extend(cards);
+            self.street = street;
+
+            // When everyone but one player is all-in there is no more betting, and the rest of
+            // the board is dealt right away
+            if self.seats.iter().filter(|seat| seat.can_act()).count() >= 2 {
+                self.to_act = self.next_to_act(self.button);
+                return Ok(());
+            }
+        }
+    }
+
+    fn deal_street(&mut self, street: Street) -> Result<Vec<Card>, GameError> {
+        let count = if street == Street::Flop { 3 } else { 1 };
+
+        self.deck.burn().map_err(|_| GameError::NotEnoughCards)?;
+        let cards = self
+            .deck
+            .deal(count)
+            .map_err(|_| GameError::NotEnoughCards)?;
+
+        let board: Vec<Card> = self.board.iter().chain(&cards).cloned().collect();
+        check_dealt(&self.seats, &board)?;
+
+        Ok(cards)
+    }
+
+    fn reset_bets(&mut self) {
+        for seat in self.seats.iter_mut() {
+            seat.bet = 0;
+        }
+        self.current_bet = 0;
+        self.min_raise = self.big_blind;
+        self.raises = 0;
+        self.acted.fill(false);
+    }
+
+    // The next seat after `from` that has to call a bet or hasn't acted yet on this street
+    fn next_to_act(&self, from: usize) -> Option<usize> {
+        let n = self.seats.len();
+
+        (1..=n).map(|i| (from + i) % n).find(|&i| {
+            let seat = &self.seats[i];
+            let others_can_act = self
+                .seats
+                .iter()
+                .enumerate()
+                .any(|(j, other)| j != i && other.can_act());
+
+            seat.can_act() && (seat.bet < self.current_bet || (!self.acted[i] && others_can_act))
+        })
+    }
+
+    fn showdown(&mut self) -> Result<(), GameError> {
+        let hands = self
+            .seats
+            .iter()
+            .map(|seat| {
+                seat.in_hand()
+                    .then(|| {
+                        let cards: Vec<Card> =
+                            seat.hole_cards.iter().chain(&self.board).cloned().collect();
+                        Eval::from(cards).evaluate_best()
+                    })
+                    .transpose()
+            })
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(GameError::InvalidCards)?;
+
+        self.finish(hands);
+        Ok(())
+    }
+
+    fn finish(&mut self, hands: Vec<Option<BestHand>>) {
+        let contributions: Vec<u64> = self.seats.iter().map(|seat| seat.contributed).collect();
+        let showdown: Vec<Option<Option<&HandValue>>> = self
+            .seats
+            .iter()
+            .zip(&hands)
+            .map(|(seat, hand)| {
+                seat.in_hand()
+                    .then(|| hand.as_ref().map(|hand| &hand.value))
+            })
+            .collect();
+
+        let Settlement { pots, payouts, .. } = pot::settle(&contributions, &showdown, self.button)
+            .expect("a player is left in the hand");
+
+        for (seat, &payout) in self.seats.iter_mut().zip(&payouts) {
+            seat.stack += payout;
+        }
+
+        let stack_changes = self
+            .seats
+            .iter()
+            .zip(&self.starting_stacks)
+            .map(|(seat, &start)| seat.stack as i64 - start as i64)
+            .collect();
+
+        self.to_act = None;
+        self.result = Some(HandResult {
+            pots,
+            payouts,
+            stack_changes,
+            hands,
+        });
+    }
+}
+
+// Any deck can be given to a hand, so the cards are checked as they are dealt
+fn check_dealt(seats: &[Seat], board: &[Card]) -> Result<(), GameError> {
+    let cards: Vec<Card> = seats
+        .iter()
+        .flat_map(|seat| &seat.hole_cards)
+        .chain(board)
+        .cloned()
+        .collect();
+
+    validate_cards(&cards, 0, cards.len()).map_err(GameError::InvalidCards)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use crate::hand_ranks::HandRanking;
+
+    use super::*;
+
+    fn cards(cards: &[&str]) -> Vec<Card> {
+        cards.iter().map(|c| Card::from_str(c).unwrap()).collect()
+    }
+
+    // Heads-up with the button on seat 0, so seat 1 is dealt first
+    fn heads_up(stacks: &[u64], deal: &[&str]) -> Hand {
+        Hand::new(stacks, 0, 1, 2, Deck::from_cards(cards(deal))).unwrap()
+    }
+
+    const HEADS_UP_DEAL: [&str; 13] = [
+        "kh", "ah", "ks", "as", "2h", "2r", "7k", "9s", "3h", "4k", "3r", "jh", "tk",
+    ];
+
+    #[test]
+    fn test_blinds_and_first_to_act() {
+        let mut deck = Deck::new();
+        deck.shuffle_with_seed(1);
+        let hand = Hand::new(&[100, 100, 100, 100], 0, 1, 2, deck).unwrap();
+
+        assert_eq!(hand.seats[1].bet, 1);
+        assert_eq!(hand.seats[2].bet, 2);
+        assert_eq!(hand.to_act, Some(3));
+        assert_eq!(hand.to_call(), 2);
+        assert_eq!(hand.pot(), 3);
+        assert!(hand.seats.iter().all(|seat| seat.hole_cards.len() == 2))
+    }
+
+    #[test]
+    fn test_heads_up_button_acts_first_before_the_flop() {
+        let mut hand = heads_up(&[100, 100], &HEADS_UP_DEAL);
+
+        assert_eq!(hand.seats[0].bet, 1);
+        assert_eq!(hand.to_act, Some(0));
+
+        hand.act(Action::Call).unwrap();
+        hand.act(Action::Check).unwrap();
+
+        assert_eq!(hand.street, Street::Flop);
+        assert_eq!(hand.board, cards(&["2r", "7k", "9s"]));
+        assert_eq!(hand.to_act, Some(1))
+    }
+
+    #[test]
+    fn test_fold_wins_the_blinds() {
+        let mut deck = Deck::new();
+        deck.shuffle_with_seed(2);
+        let mut hand = Hand::new(&[100, 100, 100], 0, 1, 2, deck).unwrap();
+
+        hand.act(Action::Fold).unwrap();
+        hand.act(Action::Fold).unwrap();
+
+        let result = hand.result.unwrap();
+        assert_eq!(result.stack_changes, vec![0, -1, 1]);
+        assert_eq!(result.hands, vec![None, None, None])
+    }
+
+    #[test]
+    fn test_showdown() {
+        let mut hand = heads_up(&[100, 100], &HEADS_UP_DEAL);
+
+        hand.act(Action::Raise(6)).unwrap();
+        hand.act(Action::Call).unwrap();
+        for _ in 0..3 {
+            hand.act(Action::Check).unwrap();
+            hand.act(Action::Check).unwrap();
+        }
+
+        assert!(hand.is_over());
+        assert_eq!(hand.board.len(), 5);
+
+        let result = hand.result.unwrap();
+        assert_eq!(result.stack_changes, vec![6, -6]);
+        assert_eq!(
+            result.hands[0].as_ref().unwrap().ranking(),
+            HandRanking::Pair
+        )
+    }
+
+    #[test]
+    fn test_side_pots() {
+        // The short stack has aces and wins the main pot, kings win the side pot against
+        // queens, and the rest of the big stack's all-in is returned
+        let deal = [
+            "ah", "kh", "qh", "as", "ks", "qs", "2h", "2r", "7k", "9r", "3h", "3k", "3r", "4s",
+        ];
+        let mut hand = Hand::new(&[100, 20, 50], 0, 1, 2, Deck::from_cards(cards(&deal))).unwrap();
+
+        hand.act(Action::AllIn).unwrap();
+        hand.act(Action::AllIn).unwrap();
+        hand.act(Action::AllIn).unwrap();
+
+        let result = hand.result.unwrap();
+        assert_eq!(result.pots.len(), 2);
+        assert_eq!(result.pots[1].winners, vec![2]);
+        assert_eq!(result.payouts, vec![50, 60, 60]);
+        assert_eq!(result.stack_changes, vec![-50, 40, 10])
+    }
+
+    #[test]
+    fn test_split_pot_odd_chip() {
+        // The button and the big blind both play the board after the small blind folds, and the
+        // odd chip goes to the winner closest to the left of the button
+        let deal = [
+            "4r", "2h", "2s", "5r", "3h", "3s", "6h", "ah", "ar", "ak", "7h", "kh", "7r", "kk",
+        ];
+        let mut hand =
+            Hand::new(&[100, 100, 100], 0, 1, 2, Deck::from_cards(cards(&deal))).unwrap();
+
+        hand.act(Action::Call).unwrap();
+        hand.act(Action::Fold).unwrap();
+        hand.act(Action::Check).unwrap();
+        for _ in 0..3 {
+            hand.act(Action::Check).unwrap();
+            hand.act(Action::Check).unwrap();
+        }
+
+        assert_eq!(hand.result.unwrap().payouts, vec![2, 0, 3])
+    }
+
+    #[test]
+    fn test_min_raise() {
+        let mut hand = heads_up(&[100, 100], &HEADS_UP_DEAL);
+
+        hand.act(Action::Raise(6)).unwrap();
+
+        // The last raise was 4, so a reraise has to be to at least 10
+        assert_eq!(
+            hand.act(Action::Raise(9)),
+            Err(GameError::IllegalAction(BettingError::BetTooSmall))
+        );
+        hand.act(Action::Raise(10)).unwrap();
+        assert_eq!(hand.min_raise, 4);
+        assert_eq!(hand.current_bet, 10)
+    }
+
+    #[test]
+    fn test_short_all_in_does_not_reopen_raising() {
+        let mut deck = Deck::new();
+        deck.shuffle_with_seed(3);
+        let mut hand = Hand::new(&[100, 100, 8], 0, 1, 2, deck).unwrap();
+
+        // The button raises to 6, the small blind calls and the big blind goes all-in for 8,
+        // which is less than a full raise
+        hand.act(Action::Raise(6)).unwrap();
+        hand.act(Action::Call).unwrap();
+        hand.act(Action::AllIn).unwrap();
+
+        assert_eq!(hand.to_act, Some(0));
+        assert_eq!(
+            hand.act(Action::Raise(20)),
+            Err(GameError::IllegalAction(BettingError::RaisingClosed))
+        );
+        hand.act(Action::Call).unwrap();
+        hand.act(Action::Call).unwrap();
+
+        assert_eq!(hand.street, Street::Flop);
+        assert_eq!(hand.pot(), 24)
+    }
+
+    #[test]
+    fn test_pot_limit_hand() {
+        let mut hand = Hand::with_structure(
+            &[100, 100],
+            0,
+            1,
+            2,
+            BettingStructure::PotLimit,
+            Deck::from_cards(cards(&HEADS_UP_DEAL)),
+        )
+        .unwrap();
+
+        // Calling 1 makes the pot 4, so the button can raise to 6
+        assert_eq!(
+            hand.act(Action::AllIn),
+            Err(GameError::IllegalAction(BettingError::BetTooLarge))
+        );
+        hand.act(Action::Raise(6)).unwrap();
+
+        // Calling 4 makes the pot 12, so the big blind can raise to 18
+        assert_eq!(
+            hand.structure.raise_range(&hand.betting_state().unwrap()),
+            Ok((10, 18))
+        )
+    }
+
+    #[test]
+    fn test_fixed_limit_hand() {
+        let mut hand = Hand::with_structure(
+            &[100, 100],
+            0,
+            1,
+            2,
+            BettingStructure::FixedLimit,
+            Deck::from_cards(cards(&HEADS_UP_DEAL)),
+        )
+        .unwrap();
+
+        // The big blind is the first bet, so after three raises the betting is capped
+        hand.act(Action::Raise(4)).unwrap();
+        hand.act(Action::Raise(6)).unwrap();
+        hand.act(Action::Raise(8)).unwrap();
+
+        assert_eq!(
+            hand.act(Action::Raise(10)),
+            Err(GameError::IllegalAction(BettingError::RaisesCapped))
+        );
+        hand.act(Action::Call).unwrap();
+
+        // After the flop the bets are still the small bet of 2
+        assert_eq!(
+            hand.act(Action::Bet(4)),
+            Err(GameError::IllegalAction(BettingError::BetTooLarge))
+        );
+        hand.act(Action::Bet(2)).unwrap();
+        hand.act(Action::Call).unwrap();
+
+        // And on the turn the big bet of 4
+        assert_eq!(hand.street, Street::Turn);
+        hand.act(Action::Bet(4)).unwrap()
+    }
+
+    #[test]
+    fn test_illegal_actions() {
+        let mut hand = heads_up(&[100, 100], &HEADS_UP_DEAL);
+
+        let illegal = [
+            (Action::Check, BettingError::CannotCheck),
+            (Action::Bet(4), BettingError::CannotBet),
+            (Action::Raise(3), BettingError::BetTooSmall),
+            (Action::Raise(200), BettingError::NotEnoughChips),
+        ];
+        for (action, error) in illegal {
+            assert_eq!(hand.act(action), Err(GameError::IllegalAction(error)))
+        }
+
+        hand.act(Action::Fold).unwrap();
+
+        assert_eq!(hand.act(Action::Check), Err(GameError::HandIsOver))
+    }
+
+    #[test]
+    fn test_invalid_cards_are_rejected() {
+        assert_eq!(
+            Hand::new(&[100, 100], 0, 1, 2, Deck::with_joker()).unwrap_err(),
+            GameError::InvalidCards(HandError::CardNotInDeck)
+        );
+
+        let duplicate_hole_cards = cards(&["ah", "kh", "ah", "ks"]);
+        assert_eq!(
+            Hand::new(&[100, 100], 0, 1, 2, Deck::from_cards(duplicate_hole_cards)).unwrap_err(),
+            GameError::InvalidCards(HandError::DuplicateCards)
+        );
+
+        // The board pairs a hole card, which is found when the flop is dealt
+        let duplicate_board = cards(&["ah", "kh", "as", "ks", "2r", "ah", "7r", "9k"]);
+        let mut hand = Hand::new(&[100, 100], 0, 1, 2, Deck::from_cards(duplicate_board)).unwrap();
+        hand.act(Action::Call).unwrap();
+        assert_eq!(
+            hand.act(Action::Check),
+            Err(GameError::InvalidCards(HandError::DuplicateCards))
+        );
+
+        // The bad flop is not put on the board and the hand can't go on
+        assert!(hand.board.is_empty());
+        assert_eq!(hand.street, Street::Preflop);
+        assert_eq!(hand.to_act, None);
+        assert_eq!(hand.act(Action::Check), Err(GameError::HandIsOver))
+    }
+
+    #[test]
+    fn test_not_enough_players() {
+        assert_eq!(
+            Hand::new(&[100, 0, 0], 0, 1, 2, Deck::new()).unwrap_err(),
+            GameError::NotEnoughPlayers
+        )
+    }
+}
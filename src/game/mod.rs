@@ -0,0 +1,6 @@
+pub mod betting;
+pub mod hand;
+pub mod player;
+pub mod pot;
+pub mod simulate;
+pub mod table;
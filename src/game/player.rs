@@ -0,0 +1,321 @@
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+use crate::{
+    card::{card::Card, cardvalue::CardValue},
+    eval::Eval,
+    hand_ranks::HandRanking,
+};
+
+use super::hand::{Action, Hand, Street};
+
+// What the seat whose turn it is can do. Raises are totals for the street like in `Action`.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct LegalActions {
+    // False when there is a bet to call, then the player has to fold, call or raise
+    pub check: bool,
+    // What calling puts in, 0 when the player can check
+    pub call: u64,
+    // The smallest and the largest total the player can bet or raise to, `None` when they can't
+    pub raise: Option<(u64, u64)>,
+}
+
+// Everything a player can see when it is their turn, their own hole cards but not the others
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct GameView {
+    pub seat: usize,
+    pub hole_cards: Vec<Card>,
+    pub board: Vec<Card>,
+    pub street: Street,
+    pub button: usize,
+    pub big_blind: u64,
+    pub pot: u64,
+    // The highest bet on the street and what the player has put in on it
+    pub current_bet: u64,
+    pub bet: u64,
+    pub stacks: Vec<u64>,
+    // The number of players who can still win the pot, including this one
+    pub players_in_hand: usize,
+    pub legal: LegalActions,
+}
+
+impl GameView {
+    // The view of the seat whose turn it is, `None` once the hand is over
+    pub fn from_hand(hand: &Hand) -> Option<GameView> {
+        let seat = hand.to_act?;
+        let state = hand.betting_state()?;
+
+        Some(GameView {
+            seat,
+            hole_cards: hand.seats[seat].hole_cards.clone(),
+            board: hand.board.clone(),
+            street: hand.street,
+            button: hand.button,
+            big_blind: hand.big_blind,
+            pot: hand.pot(),
+            current_bet: hand.current_bet,
+            bet: state.bet,
+            stacks: hand.seats.iter().map(|seat| seat.stack).collect(),
+            players_in_hand: hand.seats.iter().filter(|seat| seat.in_hand()).count(),
+            legal: LegalActions {
+                check: state.current_bet == state.bet,
+                call: state.to_call(),
+                raise: hand.structure.raise_range(&state).ok(),
+            },
+        })
+    }
+
+    pub fn check_or_call(&self) -> Action {
+        if self.legal.check {
+            Action::Check
+        } else {
+            Action::Call
+        }
+    }
+
+    pub fn check_or_fold(&self) -> Action {
+        if self.legal.check {
+            Action::Check
+        } else {
+            Action::Fold
+        }
+    }
+
+    // A bet or raise to the total, moved into the allowed range. Checks or calls when the
+    // player can't raise.
+    pub fn raise_to(&self, total: u64) -> Action {
+        match self.raise_range() {
+            Some((min, max)) if self.current_bet == 0 => Action::Bet(total.clamp(min, max)),
+            Some((min, max)) => Action::Raise(total.clamp(min, max)),
+            None => self.check_or_call(),
+        }
+    }
+
+    // The legal raises with the maximum never below the minimum, since a view can be built by
+    // hand with any range
+    fn raise_range(&self) -> Option<(u64, u64)> {
+        self.legal.raise.map(|(min, max)| (min, max.max(min)))
+    }
+}
+
+// A strategy that picks an action from what it can see. It is only asked when it is its turn, so
+// it is given the same seat over a hand but may sit in a different seat in the next one.
+pub trait Player {
+    fn act(&mut self, view: &GameView) -> Action;
+}
+
+// Picks any of the legal actions with the same chance, and a raise of any size. Never folds when
+// it can check.
+#[derive(Debug, Clone)]
+pub struct RandomPlayer {
+    rng: StdRng,
+}
+
+impl RandomPlayer {
+    pub fn new(seed: u64) -> RandomPlayer {
+        RandomPlayer {
+            rng: StdRng::seed_from_u64(seed),
+        }
+    }
+}
+
+impl Player for RandomPlayer {
+    fn act(&mut self, view: &GameView) -> Action {
+        let mut actions = vec![view.check_or_call()];
+        if !view.legal.check {
+            actions.push(Action::Fold);
+        }
+        if let Some((min, max)) = view.raise_range() {
+            actions.push(view.raise_to(self.rng.gen_range(min..=max)));
+        }
+
+        actions[self.rng.gen_range(0..actions.len())]
+    }
+}
+
+// Checks or calls every time and never folds or raises
+#[derive(Debug, Clone, Copy, Default)]
+pub struct CallingStation;
+
+impl Player for CallingStation {
+    fn act(&mut self, view: &GameView) -> Action {
+        view.check_or_call()
+    }
+}
+
+// Plays by a few fixed rules. Before the flop it raises big pairs and big aces, plays other pairs
+// and high cards for a small price and folds the rest. After the flop it goes by the ranking of
+// its best hand from `Eval`: it bets the pot with two pair or better, calls up to half the pot
+// with a pair and gives up with nothing.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct RuleBasedPlayer;
+
+impl RuleBasedPlayer {
+    fn preflop(&self, view: &GameView) -> Action {
+        let (high, low) = match view.hole_cards[..] {
+            [first, second] => (first.value.max(second.value), first.value.min(second.value)),
+            _ => return view.check_or_call(),
+        };
+
+        let premium = (high == low && high >= CardValue::Nine) || low >= CardValue::Q;
+        let playable = high == low || high == CardValue::A || low >= CardValue::T;
+
+        if premium {
+            view.raise_to(3 * view.current_bet.max(view.big_blind))
+        } else if playable && view.legal.call <= 3 * view.big_blind {
+            view.check_or_call()
+        } else {
+            view.check_or_fold()
+        }
+    }
+
+    fn postflop(&self, view: &GameView) -> Action {
+        let cards: Vec<Card> = view.hole_cards.iter().chain(&view.board).cloned().collect();
+        let ranking = match Eval::from(cards).evaluate_best() {
+            Ok(best) => best.ranking(),
+            Err(_) => return view.check_or_call(),
+        };
+
+        if ranking >= HandRanking::TwoPair {
+            view.raise_to(view.current_bet + view.pot + view.legal.call)
+        } else if ranking == HandRanking::Pair && 2 * view.legal.call <= view.pot {
+            view.check_or_call()
+        } else {
+            view.check_or_fold()
+        }
+    }
+}
+
+impl Player for RuleBasedPlayer {
+    fn act(&mut self, view: &GameView) -> Action {
+        match view.street {
+            Street::Preflop => self.preflop(view),
+            _ => self.postflop(view),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use rand::{rngs::StdRng, SeedableRng};
+
+    use crate::{
+        card::deck::Deck,
+        game::{betting::BettingStructure, table::Table},
+    };
+
+    use super::*;
+
+    fn cards(cards: &[&str]) -> Vec<Card> {
+        cards.iter().map(|c| Card::from_str(c).unwrap()).collect()
+    }
+
+    // Dealt one card at a time, so seat 1 gets Ah Ak and seat 0 gets 7s 2r
+    fn heads_up() -> Hand {
+        let deal = cards(&["ah", "7s", "ak", "2r"]);
+        Hand::new(&[100, 100], 0, 1, 2, Deck::from_cards(deal)).unwrap()
+    }
+
+    #[test]
+    fn test_view_of_the_seat_to_act() {
+        let hand = heads_up();
+        let view = GameView::from_hand(&hand).unwrap();
+
+        assert_eq!(view.seat, 0);
+        assert_eq!(view.hole_cards, cards(&["7s", "2r"]));
+        assert_eq!(view.pot, 3);
+        assert_eq!(
+            view.legal,
+            LegalActions {
+                check: false,
+                call: 1,
+                raise: Some((4, 100)),
+            }
+        );
+        assert_eq!(view.raise_to(1000), Action::Raise(100));
+        assert_eq!(view.check_or_fold(), Action::Fold)
+    }
+
+    #[test]
+    fn test_rule_based_player() {
+        let mut hand = heads_up();
+        let mut player = RuleBasedPlayer;
+
+        // Seven deuce folds to the big blind
+        let view = GameView::from_hand(&hand).unwrap();
+        assert_eq!(player.act(&view), Action::Fold);
+
+        // Aces raise after a call
+        hand = heads_up();
+        hand.act(Action::Call).unwrap();
+        let view = GameView::from_hand(&hand).unwrap();
+        assert_eq!(view.hole_cards, cards(&["ah", "ak"]));
+        assert_eq!(player.act(&view), Action::Raise(6))
+    }
+
+    #[test]
+    fn test_calling_stations_go_to_showdown() {
+        let mut table = Table::new(vec![100, 100, 100], 1, 2);
+        let mut rng = StdRng::seed_from_u64(3);
+        let mut players = [CallingStation, CallingStation, CallingStation];
+
+        table
+            .play_hand(&mut rng, |hand, seat| {
+                players[seat].act(&GameView::from_hand(hand).unwrap())
+            })
+            .unwrap();
+
+        assert_eq!(table.stacks.iter().sum::<u64>(), 300)
+    }
+
+    #[test]
+    fn test_pot_limit_with_a_short_big_blind() {
+        // With blinds of 1 and 6 the big blind is all-in for 2, which leaves a pot smaller than
+        // the minimum raise
+        for seed in 0..20 {
+            let mut table = Table::with_structure(vec![100, 2], 1, 6, BettingStructure::PotLimit);
+            let mut rng = StdRng::seed_from_u64(seed);
+
+            let view = GameView::from_hand(&table.deal(&mut rng).unwrap()).unwrap();
+            assert_eq!(view.legal.raise, Some((8, 8)));
+
+            let mut players: Vec<Box<dyn Player>> = vec![
+                Box::new(RandomPlayer::new(seed)),
+                Box::new(RandomPlayer::new(seed + 1)),
+            ];
+            table.play_hand_with(&mut rng, &mut players).unwrap();
+
+            assert_eq!(table.stacks.iter().sum::<u64>(), 102)
+        }
+    }
+
+    #[test]
+    fn test_bots_only_make_legal_actions() {
+        let structures = [
+            BettingStructure::NoLimit,
+            BettingStructure::PotLimit,
+            BettingStructure::FixedLimit,
+        ];
+
+        for structure in structures {
+            let mut table = Table::with_structure(vec![200; 4], 1, 2, structure);
+            let mut rng = StdRng::seed_from_u64(7);
+            let mut players: Vec<Box<dyn Player>> = vec![
+                Box::new(RandomPlayer::new(1)),
+                Box::new(RandomPlayer::new(2)),
+                Box::new(CallingStation),
+                Box::new(RuleBasedPlayer),
+            ];
+
+            for _ in 0..50 {
+                if table.players_with_chips() < 2 {
+                    break;
+                }
+                table.play_hand_with(&mut rng, &mut players).unwrap();
+            }
+
+            assert_eq!(table.stacks.iter().sum::<u64>(), 800, "{:?}", structure)
+        }
+    }
+}
@@ -0,0 +1,244 @@
+use std::fmt;
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum PotError {
+    MismatchedSeats,
+    NoPlayersLeft,
+}
+
+impl fmt::Display for PotError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PotError::MismatchedSeats => {
+                write!(
+                    f,
+                    "Contributions and hands are not given for the same seats"
+                )
+            }
+            PotError::NoPlayersLeft => write!(f, "No player is left to win the pot"),
+        }
+    }
+}
+
+// The main pot or a side pot, with the seats that put in enough to win it
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Pot {
+    pub amount: u64,
+    pub eligible: Vec<usize>,
+    pub winners: Vec<usize>,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Settlement {
+    // The main pot first, then the side pots
+    pub pots: Vec<Pot>,
+    // The part of a bet that nobody called, which goes back to the player who bet it
+    pub refunds: Vec<u64>,
+    // Everything each seat gets back, what they won from the pots and their refund
+    pub payouts: Vec<u64>,
+}
+
+// Settles the pot from what each seat put in over the hand and their hand at showdown, `None`
+// for the seats that folded. The hand can be anything that orders from worst to best, like a
+// `HandValue` or a `HandStrength`. When everyone else folded the one player left can be given
+// any hand.
+//
+// The pot is split into a main pot and side pots, so a player who is all-in only wins what
+// they could match from each of the other players. Tied players split a pot, and the odd chips
+// go one each to the tied players closest to the left of the button.
+pub fn settle<T: Ord>(
+    contributions: &[u64],
+    hands: &[Option<T>],
+    button: usize,
+) -> Result<Settlement, PotError> {
+    let n = contributions.len();
+    if hands.len() != n {
+        return Err(PotError::MismatchedSeats);
+    }
+
+    let order: Vec<usize> = (1..=n).map(|i| (button + i) % n).collect();
+    let live: Vec<usize> = order
+        .iter()
+        .copied()
+        .filter(|&seat| hands[seat].is_some())
+        .collect();
+    if live.is_empty() {
+        return Err(PotError::NoPlayersLeft);
+    }
+
+    // Nobody can call more than the second biggest contribution, so the rest of the biggest one
+    // is given back
+    let mut refunds = vec![0; n];
+    let mut sorted = contributions.to_vec();
+    sorted.sort_by(|a, b| b.cmp(a));
+    let called = sorted.get(1).copied().unwrap_or(0);
+    if let Some(seat) = (0..n).find(|&seat| contributions[seat] > called) {
+        refunds[seat] = contributions[seat] - called;
+    }
+
+    let mut levels: Vec<u64> = contributions.iter().map(|&c| c.min(called)).collect();
+    levels.sort();
+    levels.dedup();
+
+    let mut pots: Vec<Pot> = Vec::new();
+    let mut previous = 0;
+    for level in levels.into_iter().filter(|&level| level > 0) {
+        let amount: u64 = contributions
+            .iter()
+            .map(|&c| c.min(level) - c.min(previous))
+            .sum();
+        previous = level;
+
+        let eligible: Vec<usize> = live
+            .iter()
+            .copied()
+            .filter(|&seat| contributions[seat] >= level)
+            .collect();
+
+        // Chips put in by folded players above what any live player put in go to the last pot
+        // someone can win, and levels only folded players filled up stay one pot with the level
+        // below, so the odd chips of a split are only given out once
+        match pots.last_mut() {
+            Some(pot) if eligible.is_empty() || pot.eligible == eligible => pot.amount += amount,
+            _ => pots.push(Pot {
+                amount,
+                eligible: if eligible.is_empty() {
+                    live.clone()
+                } else {
+                    eligible
+                },
+                winners: Vec::new(),
+            }),
+        }
+    }
+
+    let mut payouts = refunds.clone();
+    for pot in pots.iter_mut() {
+        let best = pot.eligible.iter().map(|&seat| &hands[seat]).max();
+        pot.winners = pot
+            .eligible
+            .iter()
+            .copied()
+            .filter(|&seat| Some(&hands[seat]) == best)
+            .collect();
+
+        let each = pot.amount / pot.winners.len() as u64;
+        let odd_chips = pot.amount % pot.winners.len() as u64;
+        for (i, &winner) in pot.winners.iter().enumerate() {
+            payouts[winner] += each + u64::from((i as u64) < odd_chips);
+        }
+    }
+
+    Ok(Settlement {
+        pots,
+        refunds,
+        payouts,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_single_winner() {
+        let settlement = settle(&[10, 10, 10], &[Some(3), Some(5), Some(1)], 0).unwrap();
+
+        assert_eq!(settlement.pots.len(), 1);
+        assert_eq!(settlement.payouts, vec![0, 30, 0])
+    }
+
+    #[test]
+    fn test_side_pots() {
+        // The short stack has the best hand and wins the main pot, the side pot goes to the
+        // second best hand and the uncalled part of the big stack is given back
+        let settlement = settle(&[100, 20, 50], &[Some(1), Some(3), Some(2)], 0).unwrap();
+
+        assert_eq!(
+            settlement.pots,
+            vec![
+                Pot {
+                    amount: 60,
+                    eligible: vec![1, 2, 0],
+                    winners: vec![1],
+                },
+                Pot {
+                    amount: 60,
+                    eligible: vec![2, 0],
+                    winners: vec![2],
+                },
+            ]
+        );
+        assert_eq!(settlement.refunds, vec![50, 0, 0]);
+        assert_eq!(settlement.payouts, vec![50, 60, 60])
+    }
+
+    #[test]
+    fn test_uncalled_bet_is_refunded() {
+        let settlement = settle(&[100, 40], &[Some(1), Some(2)], 0).unwrap();
+
+        assert_eq!(settlement.refunds, vec![60, 0]);
+        assert_eq!(settlement.payouts, vec![60, 80])
+    }
+
+    #[test]
+    fn test_everyone_else_folded() {
+        let settlement = settle(&[1, 2, 6], &[None, None, Some(())], 0).unwrap();
+
+        assert_eq!(settlement.refunds, vec![0, 0, 4]);
+        assert_eq!(settlement.payouts, vec![0, 0, 9])
+    }
+
+    #[test]
+    fn test_odd_chips_go_left_of_the_button() {
+        let settlement = settle(&[5, 5, 5], &[Some(2), Some(1), Some(2)], 1).unwrap();
+        assert_eq!(settlement.payouts, vec![7, 0, 8]);
+
+        let settlement = settle(&[5, 5, 5, 5], &[Some(2), Some(2), Some(2), None], 0).unwrap();
+        assert_eq!(settlement.payouts, vec![6, 7, 7, 0])
+    }
+
+    #[test]
+    fn test_folded_chips_stay_in_the_pot() {
+        // The chips of the folded player go to the side pot the all-in player can't win
+        let settlement = settle(&[30, 10, 30], &[Some(1), Some(2), None], 0).unwrap();
+
+        assert_eq!(settlement.pots.len(), 2);
+        assert_eq!(settlement.payouts, vec![40, 30, 0]);
+
+        // Folded players put in more than the only player left, who wins all of it except the
+        // bet nobody called
+        let settlement = settle(&[10, 30, 20], &[Some(1), None, None], 0).unwrap();
+
+        assert_eq!(settlement.pots.len(), 1);
+        assert_eq!(settlement.payouts, vec![50, 10, 0])
+    }
+
+    #[test]
+    fn test_levels_with_the_same_players_are_one_pot() {
+        // The folded players put in 16 and 27, which would make three side pots for the same
+        // three players, each with an odd chip of its own
+        let settlement = settle(
+            &[40, 16, 27, 40, 40, 5],
+            &[Some(2), None, None, Some(2), Some(1), Some(1)],
+            5,
+        )
+        .unwrap();
+
+        assert_eq!(settlement.pots.len(), 2);
+        assert_eq!(settlement.pots[1].eligible, vec![0, 3, 4]);
+        assert_eq!(settlement.payouts, vec![84, 0, 0, 84, 0, 0])
+    }
+
+    #[test]
+    fn test_settle_errors() {
+        assert_eq!(
+            settle(&[10, 10], &[Some(1)], 0).unwrap_err(),
+            PotError::MismatchedSeats
+        );
+        assert_eq!(
+            settle::<u8>(&[10, 10], &[None, None], 0).unwrap_err(),
+            PotError::NoPlayersLeft
+        )
+    }
+}
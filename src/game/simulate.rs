@@ -0,0 +1,408 @@
+use std::fmt;
+
+use rand::{rngs::StdRng, SeedableRng};
+
+use super::{
+    betting::BettingStructure,
+    hand::GameError,
+    player::{GameView, Player},
+    table::Table,
+};
+
+// A bot taking part in a simulation, with the name it is reported under
+pub struct Entrant {
+    pub name: String,
+    pub player: Box<dyn Player>,
+}
+
+impl Entrant {
+    pub fn new<P: Player + 'static>(name: &str, player: P) -> Entrant {
+        Entrant {
+            name: name.to_string(),
+            player: Box::new(player),
+        }
+    }
+}
+
+// The results of one bot, with the winnings counted in big blinds of the hand they were won in
+#[derive(Debug, PartialEq, Clone, Default)]
+pub struct PlayerStats {
+    pub name: String,
+    pub hands: usize,
+    pub big_blinds: f64,
+    pub tournaments_won: usize,
+    // The sum of the squared result of every hand, for the variance
+    squares: f64,
+}
+
+impl PlayerStats {
+    fn add(&mut self, big_blinds: f64) {
+        self.hands += 1;
+        self.big_blinds += big_blinds;
+        self.squares += big_blinds * big_blinds;
+    }
+
+    pub fn bb_per_100(&self) -> f64 {
+        if self.hands == 0 {
+            return 0.0;
+        }
+
+        100.0 * self.big_blinds / self.hands as f64
+    }
+
+    // The 95% confidence interval of the win rate in big blinds per 100 hands, from the normal
+    // approximation of the mean of the hands played
+    pub fn confidence_interval(&self) -> (f64, f64) {
+        let rate = self.bb_per_100();
+        if self.hands < 2 {
+            return (rate, rate);
+        }
+
+        let n = self.hands as f64;
+        let mean = self.big_blinds / n;
+        let variance = ((self.squares - n * mean * mean) / (n - 1.0)).max(0.0);
+        let margin = 100.0 * 1.96 * (variance / n).sqrt();
+
+        (rate - margin, rate + margin)
+    }
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct Report {
+    pub hands: usize,
+    pub tournaments: usize,
+    // In the order the bots were entered
+    pub players: Vec<PlayerStats>,
+}
+
+impl fmt::Display for Report {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "{} hands, {} tournaments", self.hands, self.tournaments)?;
+        writeln!(
+            f,
+            "{:<16} {:>8} {:>10} {:>22} {:>6}",
+            "player", "hands", "bb/100", "95% interval", "wins"
+        )?;
+
+        for stats in &self.players {
+            let (low, high) = stats.confidence_interval();
+            writeln!(
+                f,
+                "{:<16} {:>8} {:>10.2} {:>22} {:>6}",
+                stats.name,
+                stats.hands,
+                stats.bb_per_100(),
+                format!("[{:.2}, {:.2}]", low, high),
+                stats.tournaments_won
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
+// Plays bots against each other at one table, either hand after hand with the stacks topped up,
+// or in sit-and-go tournaments until one bot has every chip. The cards come from a deck
+// shuffled with the seed, so the same bots with the same seed give the same report.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Simulation {
+    pub seed: u64,
+    pub starting_stack: u64,
+    pub small_blind: u64,
+    pub big_blind: u64,
+    pub structure: BettingStructure,
+    // In a sit-and-go the blinds double every this many hands
+    pub hands_per_level: usize,
+}
+
+impl Default for Simulation {
+    fn default() -> Self {
+        Simulation {
+            seed: 0,
+            starting_stack: 200,
+            small_blind: 1,
+            big_blind: 2,
+            structure: BettingStructure::NoLimit,
+            hands_per_level: 10,
+        }
+    }
+}
+
+impl Simulation {
+    // Every hand starts with every bot at the starting stack, so nobody goes broke
+    pub fn cash_game(&self, entrants: &mut [Entrant], hands: usize) -> Result<Report, GameError> {
+        check_entrants(entrants)?;
+        self.check_stakes()?;
+        let mut rng = StdRng::seed_from_u64(self.seed);
+        let mut stats = new_stats(entrants);
+        let mut table = self.table(entrants.len());
+
+        for _ in 0..hands {
+            table.stacks.fill(self.starting_stack);
+            play_hand(&mut table, &mut rng, entrants, &mut stats)?;
+        }
+
+        Ok(Report {
+            hands,
+            tournaments: 0,
+            players: stats,
+        })
+    }
+
+    pub fn sit_and_go(
+        &self,
+        entrants: &mut [Entrant],
+        tournaments: usize,
+    ) -> Result<Report, GameError> {
+        check_entrants(entrants)?;
+        self.check_stakes()?;
+        let mut rng = StdRng::seed_from_u64(self.seed);
+        let mut stats = new_stats(entrants);
+        let mut hands = 0;
+
+        for tournament in 0..tournaments {
+            let mut table = self.table(entrants.len());
+            table.button = tournament % entrants.len();
+
+            let mut played = 0;
+            while table.players_with_chips() > 1 {
+                let level = (played / self.hands_per_level.max(1)).min(32) as u32;
+                table.small_blind = self.small_blind << level;
+                table.big_blind = self.big_blind << level;
+
+                play_hand(&mut table, &mut rng, entrants, &mut stats)?;
+                played += 1;
+            }
+            hands += played;
+
+            if let Some(winner) = table.stacks.iter().position(|&stack| stack > 0) {
+                stats[winner].tournaments_won += 1;
+            }
+        }
+
+        Ok(Report {
+            hands,
+            tournaments,
+            players: stats,
+        })
+    }
+
+    // Results are counted in big blinds, so a big blind of 0 can't be reported, and with no
+    // chips to start with nobody can play
+    fn check_stakes(&self) -> Result<(), GameError> {
+        if self.small_blind == 0 || self.big_blind == 0 {
+            return Err(GameError::InvalidBlinds);
+        }
+
+        if self.starting_stack == 0 {
+            return Err(GameError::NotEnoughPlayers);
+        }
+
+        Ok(())
+    }
+
+    fn table(&self, players: usize) -> Table {
+        Table::with_structure(
+            vec![self.starting_stack; players],
+            self.small_blind,
+            self.big_blind,
+            self.structure,
+        )
+    }
+}
+
+fn check_entrants(entrants: &[Entrant]) -> Result<(), GameError> {
+    if entrants.len() < 2 {
+        return Err(GameError::NotEnoughPlayers);
+    }
+
+    Ok(())
+}
+
+fn new_stats(entrants: &[Entrant]) -> Vec<PlayerStats> {
+    entrants
+        .iter()
+        .map(|entrant| PlayerStats {
+            name: entrant.name.clone(),
+            ..PlayerStats::default()
+        })
+        .collect()
+}
+
+// Each bot sits in the seat of its own index, the seats without chips sit out
+fn play_hand(
+    table: &mut Table,
+    rng: &mut StdRng,
+    entrants: &mut [Entrant],
+    stats: &mut [PlayerStats],
+) -> Result<(), GameError> {
+    let playing: Vec<bool> = table.stacks.iter().map(|&stack| stack > 0).collect();
+    let big_blind = table.big_blind as f64;
+
+    let changes = table.play_hand(rng, |hand, seat| {
+        let view = GameView::from_hand(hand).expect("the seat is asked on its turn");
+        entrants[seat].player.act(&view)
+    })?;
+
+    for (seat, change) in changes.into_iter().enumerate() {
+        if playing[seat] {
+            stats[seat].add(change as f64 / big_blind);
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::game::{
+        hand::Action,
+        player::{CallingStation, RandomPlayer, RuleBasedPlayer},
+    };
+
+    use super::*;
+
+    struct AlwaysFold;
+
+    impl Player for AlwaysFold {
+        fn act(&mut self, view: &GameView) -> Action {
+            view.check_or_fold()
+        }
+    }
+
+    fn entrants() -> Vec<Entrant> {
+        vec![
+            Entrant::new("random", RandomPlayer::new(1)),
+            Entrant::new("calling station", CallingStation),
+            Entrant::new("rule based", RuleBasedPlayer),
+        ]
+    }
+
+    #[test]
+    fn test_cash_game_is_zero_sum() {
+        let report = Simulation::default()
+            .cash_game(&mut entrants(), 200)
+            .unwrap();
+
+        assert_eq!(report.hands, 200);
+        assert!(report.players.iter().all(|stats| stats.hands == 200));
+        let total: f64 = report.players.iter().map(|stats| stats.big_blinds).sum();
+        assert!(total.abs() < 1e-9)
+    }
+
+    #[test]
+    fn test_same_seed_same_report() {
+        let simulation = Simulation {
+            seed: 42,
+            ..Simulation::default()
+        };
+
+        assert_eq!(
+            simulation.cash_game(&mut entrants(), 100).unwrap(),
+            simulation.cash_game(&mut entrants(), 100).unwrap()
+        )
+    }
+
+    #[test]
+    fn test_folding_loses_the_blinds() {
+        let mut entrants = vec![
+            Entrant::new("folder", AlwaysFold),
+            Entrant::new("caller", CallingStation),
+        ];
+        let report = Simulation::default().cash_game(&mut entrants, 100).unwrap();
+
+        // Heads-up the folder gives up the small blind every time it is on the button, while the
+        // hands it checks down from the big blind break about even
+        let folder = &report.players[0];
+        assert!(folder.bb_per_100() < 0.0);
+
+        let (low, high) = folder.confidence_interval();
+        assert!(low <= folder.bb_per_100() && folder.bb_per_100() <= high)
+    }
+
+    #[test]
+    fn test_sit_and_go_has_one_winner() {
+        let simulation = Simulation {
+            structure: BettingStructure::PotLimit,
+            ..Simulation::default()
+        };
+        let report = simulation.sit_and_go(&mut entrants(), 5).unwrap();
+
+        assert_eq!(report.tournaments, 5);
+        assert_eq!(
+            report
+                .players
+                .iter()
+                .map(|stats| stats.tournaments_won)
+                .sum::<usize>(),
+            5
+        )
+    }
+
+    #[test]
+    fn test_not_enough_entrants() {
+        let simulation = Simulation::default();
+        let mut alone = vec![Entrant::new("alone", CallingStation)];
+
+        assert_eq!(
+            simulation.cash_game(&mut [], 10).unwrap_err(),
+            GameError::NotEnoughPlayers
+        );
+        assert_eq!(
+            simulation.sit_and_go(&mut [], 1).unwrap_err(),
+            GameError::NotEnoughPlayers
+        );
+        assert_eq!(
+            simulation.sit_and_go(&mut alone, 1).unwrap_err(),
+            GameError::NotEnoughPlayers
+        )
+    }
+
+    #[test]
+    fn test_invalid_stakes() {
+        let no_big_blind = Simulation {
+            big_blind: 0,
+            ..Simulation::default()
+        };
+        let no_small_blind = Simulation {
+            small_blind: 0,
+            ..Simulation::default()
+        };
+        let no_chips = Simulation {
+            starting_stack: 0,
+            ..Simulation::default()
+        };
+
+        assert_eq!(
+            no_big_blind.cash_game(&mut entrants(), 10).unwrap_err(),
+            GameError::InvalidBlinds
+        );
+        assert_eq!(
+            no_small_blind.sit_and_go(&mut entrants(), 1).unwrap_err(),
+            GameError::InvalidBlinds
+        );
+        assert_eq!(
+            no_chips.cash_game(&mut entrants(), 10).unwrap_err(),
+            GameError::NotEnoughPlayers
+        );
+        assert_eq!(
+            no_chips.sit_and_go(&mut entrants(), 1).unwrap_err(),
+            GameError::NotEnoughPlayers
+        )
+    }
+
+    #[test]
+    fn test_confidence_interval() {
+        let mut stats = PlayerStats::default();
+        assert_eq!(stats.confidence_interval(), (0.0, 0.0));
+
+        for big_blinds in [1.0, -1.0, 1.0, -1.0] {
+            stats.add(big_blinds);
+        }
+
+        // The hands have a standard deviation of 2/sqrt(3), so the mean one of 1/sqrt(3)
+        let (low, high) = stats.confidence_interval();
+        let margin = 100.0 * 1.96 / 3f64.sqrt();
+        assert!((low + margin).abs() < 1e-9 && (high - margin).abs() < 1e-9)
+    }
+}
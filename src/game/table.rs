@@ -0,0 +1,172 @@
+use rand::Rng;
+
+use crate::card::deck::Deck;
+
+use super::{
+    betting::BettingStructure,
+    hand::{Action, GameError, Hand},
+    player::{GameView, Player},
+};
+
+// The seats around a table and the chips in front of them, carried over from hand to hand.
+// A seat with no chips left sits out until it gets chips again.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Table {
+    pub stacks: Vec<u64>,
+    pub button: usize,
+    pub small_blind: u64,
+    pub big_blind: u64,
+    pub structure: BettingStructure,
+}
+
+impl Table {
+    pub fn new(stacks: Vec<u64>, small_blind: u64, big_blind: u64) -> Table {
+        Table::with_structure(stacks, small_blind, big_blind, BettingStructure::NoLimit)
+    }
+
+    pub fn with_structure(
+        stacks: Vec<u64>,
+        small_blind: u64,
+        big_blind: u64,
+        structure: BettingStructure,
+    ) -> Table {
+        Table {
+            stacks,
+            button: 0,
+            small_blind,
+            big_blind,
+            structure,
+        }
+    }
+
+    // Shuffles a new deck with the rng and deals the next hand
+    pub fn deal<R: Rng + ?Sized>(&self, rng: &mut R) -> Result<Hand, GameError> {
+        let mut deck = Deck::new();
+        deck.shuffle(rng);
+
+        Hand::with_structure(
+            &self.stacks,
+            self.button,
+            self.small_blind,
+            self.big_blind,
+            self.structure,
+            deck,
+        )
+    }
+
+    // Takes over the stacks from a finished hand and moves the button to the next seat with
+    // chips. Returns how much the stack of each seat went up or down.
+    pub fn finish(&mut self, hand: &Hand) -> Result<Vec<i64>, GameError> {
+        let result = hand.result.as_ref().ok_or(GameError::HandNotOver)?;
+
+        self.stacks = hand.seats.iter().map(|seat| seat.stack).collect();
+
+        let n = self.stacks.len();
+        self.button = (1..=n)
+            .map(|i| (hand.button + i) % n)
+            .find(|&seat| self.stacks[seat] > 0)
+            .unwrap_or(hand.button);
+
+        Ok(result.stack_changes.clone())
+    }
+
+    // Plays a whole hand, asking `decide` for the action of each seat when it is their turn
+    pub fn play_hand<R, F>(&mut self, rng: &mut R, mut decide: F) -> Result<Vec<i64>, GameError>
+    where
+        R: Rng + ?Sized,
+        F: FnMut(&Hand, usize) -> Action,
+    {
+        let mut hand = self.deal(rng)?;
+
+        while let Some(seat) = hand.to_act {
+            let action = decide(&hand, seat);
+            hand.act(action)?;
+        }
+
+        self.finish(&hand)
+    }
+
+    // Plays a whole hand with a player in every seat
+    pub fn play_hand_with<R: Rng + ?Sized>(
+        &mut self,
+        rng: &mut R,
+        players: &mut [Box<dyn Player>],
+    ) -> Result<Vec<i64>, GameError> {
+        self.play_hand(rng, |hand, seat| {
+            let view = GameView::from_hand(hand).expect("the seat is asked on its turn");
+            players[seat].act(&view)
+        })
+    }
+
+    pub fn players_with_chips(&self) -> usize {
+        self.stacks.iter().filter(|&&stack| stack > 0).count()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::{rngs::StdRng, SeedableRng};
+
+    use super::*;
+
+    fn check_or_call(hand: &Hand, _: usize) -> Action {
+        if hand.to_call() > 0 {
+            Action::Call
+        } else {
+            Action::Check
+        }
+    }
+
+    #[test]
+    fn test_button_moves_after_each_hand() {
+        let mut table = Table::new(vec![100, 100, 100], 1, 2);
+        let mut rng = StdRng::seed_from_u64(1);
+
+        table.play_hand(&mut rng, check_or_call).unwrap();
+        assert_eq!(table.button, 1);
+
+        table.play_hand(&mut rng, check_or_call).unwrap();
+        assert_eq!(table.button, 2)
+    }
+
+    #[test]
+    fn test_button_skips_seats_without_chips() {
+        let mut table = Table::new(vec![100, 0, 100], 1, 2);
+        let mut rng = StdRng::seed_from_u64(2);
+
+        table.play_hand(&mut rng, |_, _| Action::Fold).unwrap();
+
+        assert_eq!(table.button, 2);
+        assert_eq!(table.stacks[1], 0)
+    }
+
+    #[test]
+    fn test_chips_are_never_lost() {
+        let mut table = Table::new(vec![50, 80, 120, 30], 1, 2);
+        let mut rng = StdRng::seed_from_u64(3);
+
+        for i in 0..200 {
+            if table.players_with_chips() < 2 {
+                break;
+            }
+
+            let changes = table
+                .play_hand(&mut rng, |hand, _| match i % 3 {
+                    0 => Action::AllIn,
+                    _ => check_or_call(hand, 0),
+                })
+                .unwrap();
+
+            assert_eq!(changes.iter().sum::<i64>(), 0);
+            assert_eq!(table.stacks.iter().sum::<u64>(), 280)
+        }
+    }
+
+    #[test]
+    fn test_finish_needs_finished_hand() {
+        let mut table = Table::new(vec![100, 100], 1, 2);
+        let hand = table.deal(&mut StdRng::seed_from_u64(4)).unwrap();
+
+        assert_eq!(table.finish(&hand), Err(GameError::HandNotOver))
+    }
+}
@@ -1,8 +1,10 @@
-use std::fmt::Display;
+use std::{cmp::Ordering, fmt::Display};
 
 use serde::{Deserialize, Serialize};
 
-#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, PartialOrd, Ord, Clone)]
+use crate::card::cardvalue::CardValue;
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Hash)]
 pub enum HandRanking {
     HighCard,
     Pair,
@@ -14,6 +16,39 @@ pub enum HandRanking {
     FourOfAKind,
     StraightFlush,
     RoyalStraightFlush,
+    // Only possible when playing with wild cards
+    FiveOfAKind,
+}
+
+// The rules a hand is played with. Short-deck hold'em removes the twos through fives from the
+// deck, lets A-6-7-8-9 be the lowest straight and ranks a flush above a full house.
+#[derive(
+    Serialize, Deserialize, Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Hash, Default,
+)]
+pub enum Rules {
+    #[default]
+    Standard,
+    ShortDeck,
+}
+
+impl Rules {
+    pub fn includes(&self, value: CardValue) -> bool {
+        match self {
+            Rules::Standard => true,
+            Rules::ShortDeck => value >= CardValue::Six,
+        }
+    }
+}
+
+impl HandRanking {
+    // The position of the ranking from weakest to strongest under the given rules
+    pub fn strength(&self, rules: Rules) -> u8 {
+        match (rules, self) {
+            (Rules::ShortDeck, HandRanking::Flush) => HandRanking::FullHouse as u8,
+            (Rules::ShortDeck, HandRanking::FullHouse) => HandRanking::Flush as u8,
+            _ => *self as u8,
+        }
+    }
 }
 
 impl Display for HandRanking {
@@ -29,6 +64,37 @@ impl Display for HandRanking {
             HandRanking::FourOfAKind => write!(f, "Four of a kind"),
             HandRanking::StraightFlush => write!(f, "Straight flush"),
             HandRanking::RoyalStraightFlush => write!(f, "Royal straight flush"),
+            HandRanking::FiveOfAKind => write!(f, "Five of a kind"),
         }
     }
 }
+
+// A `HandValue` is the ranking of a hand together with the card values used to break ties
+// between two hands of the same ranking. The values are ordered by importance, so pairs and
+// trips come before kickers, e.g. two pair kings and jacks with a three is [K, J, Three].
+//
+// Because the ranking is compared first and the tie-break values second, comparing two
+// `HandValue`s tells which hand wins, and equal values means the pot is split. The rankings are
+// compared in the order of the rules the hand was evaluated with.
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone, Hash)]
+pub struct HandValue {
+    pub ranking: HandRanking,
+    pub ranks: Vec<CardValue>,
+    pub rules: Rules,
+}
+
+impl Ord for HandValue {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.ranking
+            .strength(self.rules)
+            .cmp(&other.ranking.strength(other.rules))
+            .then_with(|| self.ranks.cmp(&other.ranks))
+            .then_with(|| self.rules.cmp(&other.rules))
+    }
+}
+
+impl PartialOrd for HandValue {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
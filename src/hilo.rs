@@ -0,0 +1,206 @@
+use crate::{
+    card::card::Card,
+    eval::{validate_cards, BestHand, Eval, HandError},
+    lowball::{self, LowValue},
+    omaha,
+};
+
+// The result of a Hi/Lo showdown, where the best high hand and the best eight-or-better low
+// hand each win half of the pot. Players are expected to be given in seat order, starting
+// to the left of the button, since odd chips are handed out in that order.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct HiLo {
+    pub highs: Vec<BestHand>,
+    // The best low of each player, `None` when the player has no qualifying low
+    pub lows: Vec<Option<LowValue>>,
+    pub high_winners: Vec<usize>,
+    // Empty when no player has a qualifying low, then the high hand wins the whole pot
+    pub low_winners: Vec<usize>,
+}
+
+impl HiLo {
+    fn from(highs: Vec<BestHand>, lows: Vec<Option<LowValue>>) -> HiLo {
+        let high_winners = match highs.iter().map(|hand| &hand.value).max() {
+            Some(best) => (0..highs.len())
+                .filter(|&i| highs[i].value == *best)
+                .collect(),
+            None => Vec::new(),
+        };
+
+        let low_winners = match lows.iter().flatten().max() {
+            Some(best) => (0..lows.len())
+                .filter(|&i| lows[i].as_ref() == Some(best))
+                .collect(),
+            None => Vec::new(),
+        };
+
+        HiLo {
+            highs,
+            lows,
+            high_winners,
+            low_winners,
+        }
+    }
+
+    // Splits the pot between the high and low winners and returns what each player wins.
+    // When the pot can't be halved the odd chip goes to the high hand, so a player who ties for
+    // the low with one other player and loses the high is quartered and gets a fourth of the pot.
+    pub fn split(&self, pot: u64) -> Vec<u64> {
+        let mut payouts = vec![0; self.highs.len()];
+
+        if self.low_winners.is_empty() {
+            share(pot, &self.high_winners, &mut payouts);
+            return payouts;
+        }
+
+        let low_half = pot / 2;
+        share(pot - low_half, &self.high_winners, &mut payouts);
+        share(low_half, &self.low_winners, &mut payouts);
+
+        payouts
+    }
+}
+
+fn share(amount: u64, winners: &[usize], payouts: &mut [u64]) {
+    if winners.is_empty() {
+        return;
+    }
+
+    let each = amount / winners.len() as u64;
+    let odd_chips = amount % winners.len() as u64;
+
+    for (i, &winner) in winners.iter().enumerate() {
+        payouts[winner] += each + u64::from((i as u64) < odd_chips);
+    }
+}
+
+// Omaha Hi/Lo, where both the high and the low hand must use exactly two hole cards
+pub fn omaha_showdown(board: &[Card], players: &[Vec<Card>]) -> Result<HiLo, HandError> {
+    validate(board, players)?;
+
+    let highs = players
+        .iter()
+        .map(|hole_cards| omaha::evaluate(hole_cards, board))
+        .collect::<Result<Vec<BestHand>, HandError>>()?;
+    let lows = players
+        .iter()
+        .map(|hole_cards| omaha::evaluate_low(hole_cards, board))
+        .collect::<Result<Vec<Option<LowValue>>, HandError>>()?;
+
+    Ok(HiLo::from(highs, lows))
+}
+
+// Stud Hi/Lo, where each player has seven cards of their own and there is no board
+pub fn stud_showdown(players: &[Vec<Card>]) -> Result<HiLo, HandError> {
+    validate(&[], players)?;
+
+    let highs = players
+        .iter()
+        .map(|cards| Eval::from(cards.clone()).evaluate_best())
+        .collect::<Result<Vec<BestHand>, HandError>>()?;
+    let lows = players
+        .iter()
+        .map(|cards| lowball::eight_or_better(cards))
+        .collect::<Result<Vec<Option<LowValue>>, HandError>>()?;
+
+    Ok(HiLo::from(highs, lows))
+}
+
+fn validate(board: &[Card], players: &[Vec<Card>]) -> Result<(), HandError> {
+    let all_cards: Vec<Card> = board
+        .iter()
+        .chain(players.iter().flatten())
+        .cloned()
+        .collect();
+
+    validate_cards(&all_cards, 0, 52)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use crate::hand_ranks::HandRanking;
+
+    use super::*;
+
+    fn cards(cards: &[&str]) -> Vec<Card> {
+        cards.iter().map(|c| Card::from_str(c).unwrap()).collect()
+    }
+
+    #[test]
+    fn test_omaha_high_and_low_split() {
+        let board = cards(&["2h", "4s", "7r", "kk", "qh"]);
+        let players = vec![
+            cards(&["kh", "ks", "jr", "js"]),
+            cards(&["ah", "3s", "9r", "9s"]),
+        ];
+
+        let hilo = omaha_showdown(&board, &players).unwrap();
+
+        assert_eq!(hilo.high_winners, vec![0]);
+        assert_eq!(hilo.low_winners, vec![1]);
+        assert_eq!(hilo.highs[0].ranking(), HandRanking::ThreeOfAKind);
+        assert_eq!(hilo.split(101), vec![51, 50])
+    }
+
+    #[test]
+    fn test_omaha_no_low_scoops() {
+        let board = cards(&["2h", "9s", "tr", "kk", "qh"]);
+        let players = vec![
+            cards(&["kh", "ks", "jr", "js"]),
+            cards(&["ah", "3s", "4r", "5s"]),
+        ];
+
+        let hilo = omaha_showdown(&board, &players).unwrap();
+
+        assert!(hilo.low_winners.is_empty());
+        assert_eq!(hilo.lows, vec![None, None]);
+        assert_eq!(hilo.split(100), vec![100, 0])
+    }
+
+    #[test]
+    fn test_omaha_quartered() {
+        // Both players have the same low, the first player also has the high hand
+        let board = cards(&["2h", "4s", "7r", "kk", "kh"]);
+        let players = vec![
+            cards(&["ah", "3s", "ks", "9r"]),
+            cards(&["ar", "3r", "qs", "jr"]),
+        ];
+
+        let hilo = omaha_showdown(&board, &players).unwrap();
+
+        assert_eq!(hilo.high_winners, vec![0]);
+        assert_eq!(hilo.low_winners, vec![0, 1]);
+        assert_eq!(hilo.split(100), vec![75, 25])
+    }
+
+    #[test]
+    fn test_stud_showdown() {
+        let players = vec![
+            cards(&["ah", "2s", "3r", "5k", "8h", "kk", "kh"]),
+            cards(&["qh", "qs", "qr", "jk", "jh", "9r", "6h"]),
+            cards(&["as", "2k", "4r", "6k", "7h", "ts", "js"]),
+        ];
+
+        let hilo = stud_showdown(&players).unwrap();
+
+        assert_eq!(hilo.high_winners, vec![1]);
+        assert_eq!(hilo.low_winners, vec![2]);
+        assert_eq!(hilo.split(11), vec![0, 6, 5])
+    }
+
+    #[test]
+    fn test_showdown_duplicate_cards() {
+        let board = cards(&["2h", "4s", "7r", "kk", "kh"]);
+        let players = vec![
+            cards(&["ah", "3s", "ks", "9r"]),
+            cards(&["ah", "3r", "qs", "jr"]),
+        ];
+
+        assert_eq!(
+            omaha_showdown(&board, &players).unwrap_err(),
+            HandError::DuplicateCards
+        )
+    }
+}
@@ -0,0 +1,26 @@
+pub mod card;
+pub mod describe;
+pub mod equity;
+pub mod eval;
+pub mod game;
+pub mod hand_ranks;
+pub mod hilo;
+pub mod lookup;
+pub mod lowball;
+pub mod omaha;
+pub mod outs;
+pub mod range;
+pub mod showdown;
+pub mod three_card;
+pub mod wild;
+
+pub use card::{
+    card::{Card, CardError},
+    cardvalue::CardValue,
+    deck::{Deck, DeckError},
+    suit::Suit,
+};
+pub use describe::HandDescription;
+pub use eval::{BestHand, Eval, HandError};
+pub use hand_ranks::{HandRanking, HandValue, Rules};
+pub use range::{Combo, Range, RangeError};
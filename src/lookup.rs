@@ -0,0 +1,328 @@
+use std::sync::OnceLock;
+
+use crate::{
+    card::{card::Card, cardvalue::CardValue, suit::Suit},
+    eval::{Eval, HandError},
+    hand_ranks::{HandRanking, HandValue, Rules},
+};
+
+// One prime for each card value from two to ace. Multiplying the primes of the five card values
+// gives a number that is unique for each combination of values, no matter the order of the cards.
+const PRIMES: [u32; 13] = [2, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37, 41];
+
+// The strength of a five card hand. There are 7462 distinct five card hands, the weakest
+// (7-5-4-3-2 offsuit) has strength 1 and a royal straight flush has strength 7462.
+// Two hands with the same strength split the pot.
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Hash)]
+pub struct HandStrength(u16);
+
+impl HandStrength {
+    pub fn ranking(&self) -> HandRanking {
+        tables().values[self.0 as usize].ranking
+    }
+
+    pub fn value(&self) -> HandValue {
+        tables().values[self.0 as usize].clone()
+    }
+}
+
+struct Tables {
+    // Hands where all five cards have the same suit, indexed by a bit for each card value
+    flushes: Vec<u16>,
+    // Hands with five different card values that are not a flush, indexed the same way
+    unique: Vec<u16>,
+    // Hands with at least two cards of the same value, sorted by the product of their primes
+    products: Vec<(u32, u16)>,
+    // The `HandValue` of every strength, with an unused entry for strength zero
+    values: Vec<HandValue>,
+}
+
+fn tables() -> &'static Tables {
+    static TABLES: OnceLock<Tables> = OnceLock::new();
+    TABLES.get_or_init(build_tables)
+}
+
+// The tables are built once by running `Eval` on one hand of every possible combination
+// of card values, both as a flush and not, and sorting the results by their `HandValue`.
+fn build_tables() -> Tables {
+    let mut hands: Vec<(Vec<Card>, HandValue)> = Vec::new();
+
+    for values in value_combinations(0, 5) {
+        let has_duplicates = values.windows(2).any(|v| v[0] == v[1]);
+
+        // Within a group of equal values each card gets its own suit, so there are no duplicates
+        let cards: Vec<Card> = values
+            .iter()
+            .enumerate()
+            .map(|(i, &value)| {
+                let seen = values[..i].iter().filter(|&&v| v == value).count();
+                Card {
+                    value,
+                    suit: Suit::ALL[if has_duplicates { seen } else { i % 2 }],
+                }
+            })
+            .collect();
+        hands.push((cards.clone(), evaluate_with(cards)));
+
+        if !has_duplicates {
+            let flush: Vec<Card> = values
+                .iter()
+                .map(|&value| Card {
+                    value,
+                    suit: Suit::Heart,
+                })
+                .collect();
+            hands.push((flush.clone(), evaluate_with(flush)));
+        }
+    }
+
+    let mut values: Vec<HandValue> = hands.iter().map(|(_, value)| value.clone()).collect();
+    values.sort();
+    values.dedup();
+    values.insert(
+        0,
+        HandValue {
+            ranking: HandRanking::HighCard,
+            ranks: Vec::new(),
+            rules: Rules::Standard,
+        },
+    );
+
+    let mut tables = Tables {
+        flushes: vec![0; 1 << 13],
+        unique: vec![0; 1 << 13],
+        products: Vec::new(),
+        values,
+    };
+
+    for (cards, value) in hands {
+        let strength = tables.values.binary_search(&value).unwrap() as u16;
+        let (rank_bits, product, is_flush) = fingerprint(&cards);
+
+        if is_flush {
+            tables.flushes[rank_bits] = strength;
+        } else if rank_bits.count_ones() == 5 {
+            tables.unique[rank_bits] = strength;
+        } else {
+            tables.products.push((product, strength));
+        }
+    }
+    tables.products.sort();
+
+    tables
+}
+
+fn evaluate_with(cards: Vec<Card>) -> HandValue {
+    Eval::from(cards)
+        .evaluate_value()
+        .expect("table hands are always valid")
+}
+
+// Every sorted combination of `n` card values from `from` and up, with at most four of each value
+fn value_combinations(from: usize, n: usize) -> Vec<Vec<CardValue>> {
+    if n == 0 {
+        return vec![Vec::new()];
+    }
+
+    let mut result = Vec::new();
+    for i in from..CardValue::ALL.len() {
+        for rest in value_combinations(i, n - 1) {
+            if rest.iter().filter(|&&v| v == CardValue::ALL[i]).count() < 4 {
+                let mut values = vec![CardValue::ALL[i]];
+                values.extend(rest);
+                result.push(values);
+            }
+        }
+    }
+
+    result
+}
+
+fn fingerprint(cards: &[Card]) -> (usize, u32, bool) {
+    let mut rank_bits = 0;
+    let mut product = 1;
+    let mut is_flush = true;
+
+    for card in cards {
+        let rank = card.value as usize - 2;
+        rank_bits |= 1 << rank;
+        product *= PRIMES[rank];
+        is_flush &= card.suit == cards[0].suit;
+    }
+
+    (rank_bits, product, is_flush)
+}
+
+fn strength(tables: &Tables, cards: &[Card]) -> HandStrength {
+    let (rank_bits, product, is_flush) = fingerprint(cards);
+
+    if is_flush {
+        return HandStrength(tables.flushes[rank_bits]);
+    }
+
+    if rank_bits.count_ones() == 5 {
+        return HandStrength(tables.unique[rank_bits]);
+    }
+
+    let index = tables
+        .products
+        .binary_search_by_key(&product, |&(p, _)| p)
+        .expect("every product of five card values is in the table");
+    HandStrength(tables.products[index].1)
+}
+
+fn validate(cards: &[Card], min: usize, max: usize) -> Result<(), HandError> {
+    if cards.len() > max {
+        return Err(HandError::TooManyCards);
+    }
+
+    if cards.len() < min {
+        return Err(HandError::NotEnoughCards);
+    }
+
+    // One bit for each of the 52 cards, so duplicates are found without allocating
+    let mut seen: u64 = 0;
+    for card in cards {
+        if card.is_joker() {
+            return Err(HandError::CardNotInDeck);
+        }
+
+        let bit = 1 << card.index();
+        if seen & bit != 0 {
+            return Err(HandError::DuplicateCards);
+        }
+        seen |= bit;
+    }
+
+    Ok(())
+}
+
+// Gives the same result as `Eval::evaluate_value` with the standard rules, but looks the hand up in precomputed
+// tables instead of checking every ranking, and does not allocate.
+pub fn evaluate(cards: &[Card]) -> Result<HandStrength, HandError> {
+    validate(cards, 5, 5)?;
+
+    Ok(strength(tables(), cards))
+}
+
+// Gives the strength of the best five card hand out of five, six or seven cards,
+// the same way as `Eval::evaluate_best`.
+pub fn evaluate_best(cards: &[Card]) -> Result<HandStrength, HandError> {
+    validate(cards, 5, 7)?;
+
+    let tables = tables();
+    let n = cards.len();
+    let mut best = HandStrength(0);
+
+    for a in 0..n {
+        for b in a + 1..n {
+            for c in b + 1..n {
+                for d in c + 1..n {
+                    for e in d + 1..n {
+                        let hand = [cards[a], cards[b], cards[c], cards[d], cards[e]];
+                        best = best.max(strength(tables, &hand));
+                    }
+                }
+            }
+        }
+    }
+
+    Ok(best)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use crate::card::deck::Deck;
+
+    use super::*;
+
+    #[test]
+    fn test_number_of_distinct_hands() {
+        assert_eq!(tables().values.len() - 1, 7462)
+    }
+
+    #[test]
+    fn test_lookup_strength_order() {
+        let royal: Vec<Card> = ["ts", "js", "qs", "ks", "as"]
+            .map(|c| Card::from_str(c).unwrap())
+            .to_vec();
+        let worst: Vec<Card> = ["7s", "5h", "4s", "3s", "2s"]
+            .map(|c| Card::from_str(c).unwrap())
+            .to_vec();
+        let wheel: Vec<Card> = ["as", "2h", "3s", "4s", "5s"]
+            .map(|c| Card::from_str(c).unwrap())
+            .to_vec();
+        let six_high: Vec<Card> = ["6s", "2h", "3s", "4s", "5s"]
+            .map(|c| Card::from_str(c).unwrap())
+            .to_vec();
+
+        assert_eq!(evaluate(&royal).unwrap(), HandStrength(7462));
+        assert_eq!(evaluate(&worst).unwrap(), HandStrength(1));
+        assert_eq!(evaluate(&wheel).unwrap().ranking(), HandRanking::Straight);
+        assert!(evaluate(&wheel).unwrap() < evaluate(&six_high).unwrap())
+    }
+
+    #[test]
+    fn test_lookup_errors() {
+        let too_many: Vec<Card> = ["ts", "js", "qs", "ks", "as", "2h"]
+            .map(|c| Card::from_str(c).unwrap())
+            .to_vec();
+        let duplicates: Vec<Card> = ["ts", "js", "qs", "ks", "ts"]
+            .map(|c| Card::from_str(c).unwrap())
+            .to_vec();
+
+        assert_eq!(evaluate(&too_many).unwrap_err(), HandError::TooManyCards);
+        assert_eq!(
+            evaluate(&duplicates).unwrap_err(),
+            HandError::DuplicateCards
+        );
+        assert_eq!(
+            evaluate_best(&too_many[..4]).unwrap_err(),
+            HandError::NotEnoughCards
+        )
+    }
+
+    #[test]
+    fn test_lookup_best_matches_eval() {
+        let hands = [
+            ["kr", "ks", "jh", "js", "3h", "9h", "jr"],
+            ["4s", "5h", "6r", "7k", "8h", "9s", "2h"],
+            ["2h", "7h", "qh", "th", "kr", "4h", "as"],
+            ["ah", "ar", "as", "ak", "kh", "kr", "ks"],
+        ];
+
+        for hand in hands {
+            let cards: Vec<Card> = hand.map(|c| Card::from_str(c).unwrap()).to_vec();
+            let best = Eval::from(cards.clone()).evaluate_best().unwrap();
+
+            assert_eq!(evaluate_best(&cards).unwrap().value(), best.value)
+        }
+    }
+
+    #[test]
+    fn test_lookup_matches_eval_for_every_hand() {
+        let deck = Deck::new().cards().to_vec();
+
+        let mut hands = 0;
+        for a in 0..52 {
+            for b in a + 1..52 {
+                for c in b + 1..52 {
+                    for d in c + 1..52 {
+                        for e in d + 1..52 {
+                            let hand = vec![deck[a], deck[b], deck[c], deck[d], deck[e]];
+                            let strength = evaluate(&hand).unwrap();
+                            let value = Eval::from(hand).evaluate_value().unwrap();
+
+                            assert_eq!(tables().values[strength.0 as usize], value);
+                            hands += 1;
+                        }
+                    }
+                }
+            }
+        }
+
+        assert_eq!(hands, 2_598_960)
+    }
+}
@@ -0,0 +1,322 @@
+use std::{cmp::Ordering, collections::HashMap};
+
+use crate::{
+    card::{card::Card, cardvalue::CardValue},
+    eval::{combinations, validate_cards, Eval, HandError},
+    hand_ranks::{HandRanking, HandValue},
+};
+
+// The value of an ace-to-five low hand, where the ace counts as one and the lowest cards win.
+// Straights and flushes don't count, but pairs do, so the ranking is only ever high card, pair,
+// two pair, three of a kind, full house or four of a kind.
+//
+// The card values are ordered like in `HandValue`, pairs before kickers and the highest card
+// first, with the ace counted as one: 8-5-4-3-2 loses against 7-6-4-3-2.
+//
+// The ordering is turned around compared to the card values, so like `HandValue` the greater
+// `LowValue` is the better hand.
+#[derive(Debug, PartialEq, Eq, Clone, Hash)]
+pub struct LowValue {
+    pub ranking: HandRanking,
+    pub ranks: Vec<CardValue>,
+}
+
+impl Ord for LowValue {
+    fn cmp(&self, other: &Self) -> Ordering {
+        let own: Vec<u8> = self.ranks.iter().map(|&value| ace_low(value)).collect();
+        let others: Vec<u8> = other.ranks.iter().map(|&value| ace_low(value)).collect();
+
+        other
+            .ranking
+            .cmp(&self.ranking)
+            .then_with(|| others.cmp(&own))
+    }
+}
+
+impl PartialOrd for LowValue {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+pub(crate) fn ace_low(value: CardValue) -> u8 {
+    match value {
+        CardValue::A => 1,
+        value => value as u8,
+    }
+}
+
+// Finds the best eight-or-better low out of five to seven cards, as in Stud Hi/Lo. A low needs
+// five cards of different values that are all eight or lower, straights and flushes don't count
+// against it. Returns `None` when there is no qualifying low.
+pub fn eight_or_better(cards: &[Card]) -> Result<Option<LowValue>, HandError> {
+    validate_cards(cards, 5, 7)?;
+
+    Ok(qualifying_low(cards))
+}
+
+// The best low is always the five lowest different card values, so there is no need to look
+// at each combination of five cards
+pub(crate) fn qualifying_low(cards: &[Card]) -> Option<LowValue> {
+    let mut values: Vec<CardValue> = cards
+        .iter()
+        .map(|card| card.value)
+        .filter(|&value| ace_low(value) <= 8)
+        .collect();
+    values.sort_by_key(|&value| ace_low(value));
+    values.dedup();
+
+    if values.len() < 5 {
+        return None;
+    }
+
+    values.truncate(5);
+    values.reverse();
+
+    Some(LowValue {
+        ranking: HandRanking::HighCard,
+        ranks: values,
+    })
+}
+
+// Finds the best ace-to-five low out of five to seven cards, as in Razz and California lowball
+pub fn ace_to_five(cards: &[Card]) -> Result<LowValue, HandError> {
+    validate_cards(cards, 5, 7)?;
+
+    let best_low = combinations(cards, 5)
+        .iter()
+        .map(|hand| ace_to_five_value(hand))
+        .max();
+
+    best_low.ok_or(HandError::NotEnoughCards)
+}
+
+fn ace_to_five_value(hand: &[Card]) -> LowValue {
+    let mut rank_count: HashMap<CardValue, usize> = HashMap::new();
+    for card in hand {
+        *rank_count.entry(card.value).or_insert(0) += 1;
+    }
+
+    let mut groups: Vec<(usize, u8, CardValue)> = rank_count
+        .into_iter()
+        .map(|(value, count)| (count, ace_low(value), value))
+        .collect();
+    groups.sort_by(|a, b| b.cmp(a));
+
+    let counts: Vec<usize> = groups.iter().map(|&(count, _, _)| count).collect();
+    let ranking = match counts.as_slice() {
+        [4, ..] => HandRanking::FourOfAKind,
+        [3, 2] => HandRanking::FullHouse,
+        [3, ..] => HandRanking::ThreeOfAKind,
+        [2, 2, ..] => HandRanking::TwoPair,
+        [2, ..] => HandRanking::Pair,
+        _ => HandRanking::HighCard,
+    };
+
+    LowValue {
+        ranking,
+        ranks: groups.into_iter().map(|(_, _, value)| value).collect(),
+    }
+}
+
+// The value of a deuce-to-seven low hand, where the ace is always high and straights and
+// flushes count against the hand, so the best hand is 7-5-4-3-2 of different suits.
+// A-2-3-4-5 is not a straight here, only ace high.
+//
+// It is the normal `HandValue` with the ordering turned around, so the greater
+// `DeuceToSevenValue` is the better hand.
+#[derive(Debug, PartialEq, Eq, Clone, Hash)]
+pub struct DeuceToSevenValue(pub HandValue);
+
+impl Ord for DeuceToSevenValue {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other.0.cmp(&self.0)
+    }
+}
+
+impl PartialOrd for DeuceToSevenValue {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+// Finds the best deuce-to-seven low out of five to seven cards, as in Kansas City lowball
+pub fn deuce_to_seven(cards: &[Card]) -> Result<DeuceToSevenValue, HandError> {
+    validate_cards(cards, 5, 7)?;
+
+    let mut best_low: Option<DeuceToSevenValue> = None;
+    for hand in combinations(cards, 5) {
+        let mut value = Eval::from(hand).evaluate_value()?;
+
+        // The evaluator counts A-2-3-4-5 as the lowest straight, but here the ace is only high
+        let is_straight = matches!(
+            value.ranking,
+            HandRanking::Straight | HandRanking::StraightFlush
+        );
+        if is_straight && value.ranks.last() == Some(&CardValue::A) {
+            value.ranking = match value.ranking {
+                HandRanking::StraightFlush => HandRanking::Flush,
+                _ => HandRanking::HighCard,
+            };
+            value.ranks.rotate_right(1);
+        }
+
+        let low = DeuceToSevenValue(value);
+        if best_low.as_ref().is_none_or(|best| low > *best) {
+            best_low = Some(low);
+        }
+    }
+
+    best_low.ok_or(HandError::NotEnoughCards)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use super::*;
+
+    fn cards(cards: &[&str]) -> Vec<Card> {
+        cards.iter().map(|c| Card::from_str(c).unwrap()).collect()
+    }
+
+    #[test]
+    fn test_eight_or_better_wheel() {
+        let low = eight_or_better(&cards(&["ah", "2h", "3h", "4h", "5h"]))
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(
+            low.ranks,
+            vec![
+                CardValue::Five,
+                CardValue::Four,
+                CardValue::Three,
+                CardValue::Two,
+                CardValue::A
+            ]
+        )
+    }
+
+    #[test]
+    fn test_eight_or_better_picks_lowest_cards() {
+        let low = eight_or_better(&cards(&["8h", "2s", "7r", "3k", "ah", "2h", "6s"]))
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(
+            low.ranks,
+            vec![
+                CardValue::Seven,
+                CardValue::Six,
+                CardValue::Three,
+                CardValue::Two,
+                CardValue::A
+            ]
+        )
+    }
+
+    #[test]
+    fn test_eight_or_better_no_low() {
+        let paired = eight_or_better(&cards(&["ah", "ar", "3h", "4h", "5h"])).unwrap();
+        let nine = eight_or_better(&cards(&["9h", "2r", "3h", "4h", "5h", "kh", "ks"])).unwrap();
+
+        assert_eq!(paired, None);
+        assert_eq!(nine, None)
+    }
+
+    #[test]
+    fn test_ace_to_five_ignores_straights_and_flushes() {
+        let wheel = ace_to_five(&cards(&["ah", "2h", "3h", "4h", "5h"])).unwrap();
+        let six_high = ace_to_five(&cards(&["6s", "4h", "3r", "2h", "ak"])).unwrap();
+
+        assert_eq!(wheel.ranking, HandRanking::HighCard);
+        assert_eq!(wheel.ranks[0], CardValue::Five);
+        assert!(wheel > six_high)
+    }
+
+    #[test]
+    fn test_ace_to_five_pairs_count_against() {
+        let king_high = ace_to_five(&cards(&["ks", "qh", "jr", "th", "9k"])).unwrap();
+        let pair_of_aces = ace_to_five(&cards(&["as", "ah", "2r", "3h", "4k"])).unwrap();
+        let pair_of_twos = ace_to_five(&cards(&["2s", "2h", "ar", "3h", "4k"])).unwrap();
+
+        assert_eq!(pair_of_aces.ranking, HandRanking::Pair);
+        assert!(king_high > pair_of_twos);
+        assert!(pair_of_aces > pair_of_twos)
+    }
+
+    #[test]
+    fn test_ace_to_five_razz() {
+        let no_pair = ace_to_five(&cards(&["ks", "kh", "2r", "2h", "4k", "7s", "9h"])).unwrap();
+        let one_pair = ace_to_five(&cards(&["ks", "kh", "2r", "2h", "4k", "4s", "9h"])).unwrap();
+
+        assert_eq!(no_pair.ranking, HandRanking::HighCard);
+        assert_eq!(
+            no_pair.ranks,
+            vec![
+                CardValue::K,
+                CardValue::Nine,
+                CardValue::Seven,
+                CardValue::Four,
+                CardValue::Two
+            ]
+        );
+        assert_eq!(one_pair.ranking, HandRanking::Pair);
+        assert_eq!(
+            one_pair.ranks,
+            vec![
+                CardValue::Two,
+                CardValue::K,
+                CardValue::Nine,
+                CardValue::Four
+            ]
+        )
+    }
+
+    #[test]
+    fn test_deuce_to_seven_best_hand() {
+        let seven_five = deuce_to_seven(&cards(&["7s", "5h", "4r", "3h", "2k"])).unwrap();
+        let seven_six = deuce_to_seven(&cards(&["7s", "6h", "4r", "3h", "2k"])).unwrap();
+        let eight_high = deuce_to_seven(&cards(&["8s", "5h", "4r", "3h", "2k"])).unwrap();
+
+        assert!(seven_five > seven_six);
+        assert!(seven_six > eight_high)
+    }
+
+    #[test]
+    fn test_deuce_to_seven_ace_is_high() {
+        let wheel = deuce_to_seven(&cards(&["as", "5h", "4r", "3h", "2k"])).unwrap();
+        let king_high = deuce_to_seven(&cards(&["ks", "5h", "4r", "3h", "2k"])).unwrap();
+
+        assert_eq!(wheel.0.ranking, HandRanking::HighCard);
+        assert_eq!(wheel.0.ranks[0], CardValue::A);
+        assert!(king_high > wheel)
+    }
+
+    #[test]
+    fn test_deuce_to_seven_straights_and_flushes_count_against() {
+        let straight = deuce_to_seven(&cards(&["8s", "7h", "6r", "5h", "4k"])).unwrap();
+        let flush = deuce_to_seven(&cards(&["7h", "5h", "4h", "3h", "2h"])).unwrap();
+        let pair = deuce_to_seven(&cards(&["2s", "2h", "3r", "4h", "5k"])).unwrap();
+        let ace_high = deuce_to_seven(&cards(&["as", "kh", "qr", "jh", "9k"])).unwrap();
+        let two_pair = deuce_to_seven(&cards(&["ks", "kh", "qr", "qh", "ak"])).unwrap();
+
+        assert_eq!(two_pair.0.ranking, HandRanking::TwoPair);
+        assert!(ace_high > pair);
+        assert!(pair > straight);
+        assert!(straight > flush)
+    }
+
+    #[test]
+    fn test_low_value_order() {
+        let wheel = qualifying_low(&cards(&["ah", "2h", "3h", "4h", "5h"])).unwrap();
+        let six_four = qualifying_low(&cards(&["6h", "4h", "3s", "2h", "ah"])).unwrap();
+        let seven_six = qualifying_low(&cards(&["7h", "6s", "4h", "3h", "2h"])).unwrap();
+        let eight_five = qualifying_low(&cards(&["8h", "5s", "4h", "3h", "2h"])).unwrap();
+
+        assert!(wheel > six_four);
+        assert!(six_four > seven_six);
+        assert!(seven_six > eight_five)
+    }
+}
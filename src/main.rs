@@ -1,21 +1,24 @@
-use card::card::Card;
-use eval::Eval;
+use rust_poker::{Card, Eval};
 use serde::{Deserialize, Serialize};
 use tide::{Body, Request, Response};
 
-mod card;
-mod eval;
-mod hand_ranks;
-
 #[derive(Serialize, Deserialize)]
 struct GetResponse {
     hand: Vec<String>,
     rank: String,
+    description: String,
+    seed: u64,
+}
+
+#[derive(Serialize, Deserialize)]
+struct GetQuery {
+    seed: Option<u64>,
 }
 
 #[derive(Serialize, Deserialize)]
 struct PostResponse {
     rank: String,
+    description: String,
 }
 
 #[derive(Serialize, Deserialize)]
@@ -27,23 +30,32 @@ struct PostRequest {
 async fn main() -> tide::Result<()> {
     let mut app = tide::new();
 
-    app.at("/api/v1/hand").get(|_| async {
-        let cards: Vec<Card> = Card::new_hand();
+    app.at("/api/v1/hand")
+        .get(|request: Request<()>| async move {
+            let Ok(query) = request.query::<GetQuery>() else {
+                return Ok(Response::new(400));
+            };
+
+            // A random seed is picked when none is given, and returned so the hand can be dealt again
+            let seed = query.seed.unwrap_or_else(rand::random);
+            let cards: Vec<Card> = Card::new_hand_from_seed(seed);
 
-        let cards_as_string_array: Vec<String> = cards.iter().map(|c| c.to_string()).collect();
-        let eval = Eval::from(cards);
+            let cards_as_string_array: Vec<String> = cards.iter().map(|c| c.to_string()).collect();
+            let eval = Eval::from(cards);
 
-        match eval.evaluate() {
-            Ok(v) => Body::from_json(&GetResponse {
-                hand: cards_as_string_array,
-                rank: v.to_string(),
-            }),
-            Err(_) => Err(tide::Error::from_str(
-                500,
-                "Interal server error, sorry about that",
-            )),
-        }
-    });
+            match eval.describe() {
+                Ok(description) => Ok(Response::from(Body::from_json(&GetResponse {
+                    hand: cards_as_string_array,
+                    rank: description.value.ranking.to_string(),
+                    description: description.to_string(),
+                    seed,
+                })?)),
+                Err(_) => Err(tide::Error::from_str(
+                    500,
+                    "Interal server error, sorry about that",
+                )),
+            }
+        });
 
     app.at("/api/v1/hand")
         .post(|mut request: Request<()>| async move {
@@ -52,11 +64,12 @@ async fn main() -> tide::Result<()> {
             };
 
             let eval = Eval::from(req.cards);
-            match eval.evaluate() {
-                Ok(v) => {
+            match eval.evaluate().and_then(|_| eval.describe()) {
+                Ok(description) => {
                     let mut res = Response::new(200);
                     res.set_body(Body::from_json(&PostResponse {
-                        rank: v.to_string(),
+                        rank: description.value.ranking.to_string(),
+                        description: description.to_string(),
                     })?);
                     Ok(res)
                 }
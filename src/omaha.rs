@@ -0,0 +1,181 @@
+use crate::{
+    card::card::Card,
+    eval::{combinations, validate_cards, BestHand, Eval, HandError},
+    lowball::{qualifying_low, LowValue},
+};
+
+// Finds the best Omaha hand, where a player must use exactly two of their hole cards and exactly
+// three cards from the board. Works for four card Omaha as well as the five and six card variants.
+pub fn evaluate(hole_cards: &[Card], board: &[Card]) -> Result<BestHand, HandError> {
+    validate(hole_cards, board)?;
+
+    let mut best_hand: Option<BestHand> = None;
+    for hole in combinations(hole_cards, 2) {
+        for shared in combinations(board, 3) {
+            let cards: Vec<Card> = hole.iter().chain(&shared).cloned().collect();
+            let value = Eval::from(cards.clone()).evaluate_value()?;
+
+            if best_hand.as_ref().is_none_or(|best| value > best.value) {
+                best_hand = Some(BestHand { value, cards });
+            }
+        }
+    }
+
+    best_hand.ok_or(HandError::NotEnoughCards)
+}
+
+// Finds the best eight-or-better low for Omaha Hi/Lo, using the same two hole cards and three
+// board cards rule as the high hand. Returns `None` when no low can be made.
+pub fn evaluate_low(hole_cards: &[Card], board: &[Card]) -> Result<Option<LowValue>, HandError> {
+    validate(hole_cards, board)?;
+
+    let mut best_low: Option<LowValue> = None;
+    for hole in combinations(hole_cards, 2) {
+        for shared in combinations(board, 3) {
+            let cards: Vec<Card> = hole.iter().chain(&shared).cloned().collect();
+
+            if let Some(low) = qualifying_low(&cards) {
+                if best_low.as_ref().is_none_or(|best| low > *best) {
+                    best_low = Some(low);
+                }
+            }
+        }
+    }
+
+    Ok(best_low)
+}
+
+fn validate(hole_cards: &[Card], board: &[Card]) -> Result<(), HandError> {
+    validate_cards(hole_cards, 4, 6)?;
+    validate_cards(board, 3, 5)?;
+
+    let all_cards: Vec<Card> = hole_cards.iter().chain(board).cloned().collect();
+    validate_cards(&all_cards, 7, 11)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use crate::{card::cardvalue::CardValue, hand_ranks::HandRanking};
+
+    use super::*;
+
+    fn cards(cards: &[&str]) -> Vec<Card> {
+        cards.iter().map(|c| Card::from_str(c).unwrap()).collect()
+    }
+
+    #[test]
+    fn test_omaha_needs_two_hole_cards_for_flush() {
+        // Four hearts on the board is not a flush with only one heart in hand
+        let hole_cards = cards(&["ah", "ks", "kr", "2s"]);
+        let board = cards(&["3h", "7h", "9h", "jh", "qk"]);
+
+        let best_hand = evaluate(&hole_cards, &board).unwrap();
+
+        assert_eq!(best_hand.ranking(), HandRanking::Pair)
+    }
+
+    #[test]
+    fn test_omaha_cannot_play_the_board() {
+        let hole_cards = cards(&["2s", "3r", "8k", "9k"]);
+        let board = cards(&["th", "js", "qr", "kk", "ah"]);
+
+        let best_hand = evaluate(&hole_cards, &board).unwrap();
+
+        assert_eq!(best_hand.ranking(), HandRanking::Straight);
+        assert_eq!(best_hand.value.ranks[0], CardValue::Q)
+    }
+
+    #[test]
+    fn test_omaha_uses_two_and_three() {
+        let hole_cards = cards(&["ah", "ar", "kh", "kk"]);
+        let board = cards(&["as", "kr", "2h", "7s", "9k"]);
+
+        let best_hand = evaluate(&hole_cards, &board).unwrap();
+
+        let from_hole = best_hand
+            .cards
+            .iter()
+            .filter(|card| hole_cards.contains(card))
+            .count();
+
+        assert_eq!(best_hand.ranking(), HandRanking::ThreeOfAKind);
+        assert_eq!(from_hole, 2);
+        assert_eq!(best_hand.cards.len(), 5)
+    }
+
+    #[test]
+    fn test_omaha_five_and_six_cards() {
+        let board = cards(&["th", "jh", "2r", "7s", "9k"]);
+        let five_cards = cards(&["qh", "3r", "4r", "5r", "6k"]);
+        let six_cards = cards(&["qh", "3r", "4r", "5r", "6k", "kh"]);
+
+        let five_card_hand = evaluate(&five_cards, &board).unwrap();
+        let six_card_hand = evaluate(&six_cards, &board).unwrap();
+
+        assert_eq!(five_card_hand.ranking(), HandRanking::HighCard);
+        assert_eq!(six_card_hand.ranking(), HandRanking::Straight);
+        assert_eq!(six_card_hand.value.ranks[0], CardValue::K)
+    }
+
+    #[test]
+    fn test_omaha_low_needs_two_low_hole_cards() {
+        let board = cards(&["2h", "4s", "7r", "kk", "qh"]);
+
+        let one_low_card = evaluate_low(&cards(&["ah", "ks", "kr", "qs"]), &board).unwrap();
+        let two_low_cards = evaluate_low(&cards(&["ah", "3s", "kr", "qs"]), &board).unwrap();
+
+        assert_eq!(one_low_card, None);
+        assert_eq!(
+            two_low_cards.unwrap().ranks,
+            vec![
+                CardValue::Seven,
+                CardValue::Four,
+                CardValue::Three,
+                CardValue::Two,
+                CardValue::A
+            ]
+        )
+    }
+
+    #[test]
+    fn test_omaha_low_picks_best_pair_of_hole_cards() {
+        let board = cards(&["2h", "4s", "7r", "8k", "qh"]);
+
+        let low = evaluate_low(&cards(&["ah", "3s", "5r", "6s"]), &board).unwrap();
+
+        assert_eq!(
+            low.unwrap().ranks,
+            vec![
+                CardValue::Seven,
+                CardValue::Four,
+                CardValue::Three,
+                CardValue::Two,
+                CardValue::A
+            ]
+        )
+    }
+
+    #[test]
+    fn test_omaha_errors() {
+        let board = cards(&["th", "jh", "2r", "7s", "9k"]);
+
+        assert_eq!(
+            evaluate(&cards(&["ah", "ar", "kh"]), &board).unwrap_err(),
+            HandError::NotEnoughCards
+        );
+        assert_eq!(
+            evaluate(&cards(&["ah", "ar", "kh", "kk", "qs", "qh", "2h"]), &board).unwrap_err(),
+            HandError::TooManyCards
+        );
+        assert_eq!(
+            evaluate(&cards(&["ah", "ar", "kh", "th"]), &board).unwrap_err(),
+            HandError::DuplicateCards
+        );
+        assert_eq!(
+            evaluate(&cards(&["ah", "ar", "kh", "kk"]), &board[..2]).unwrap_err(),
+            HandError::NotEnoughCards
+        )
+    }
+}
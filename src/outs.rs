@@ -0,0 +1,143 @@
+use std::collections::BTreeMap;
+
+use crate::{
+    card::{card::Card, deck::Deck},
+    eval::{combinations, validate_cards, HandError},
+    hand_ranks::HandRanking,
+    lookup,
+};
+
+// The draws of a hold'em hand on the flop or the turn.
+//
+// An out is an unseen card that makes a better ranking than the player has now when it comes
+// next, so a card that pairs the board counts as an out for a player with high card. On the flop
+// the chances to improve by the river go through every turn and river, so they include draws
+// that need two more cards, like a backdoor flush, which have no outs.
+#[derive(Debug, PartialEq, Clone)]
+pub struct Outs {
+    pub current: HandRanking,
+    // The outs grouped by the ranking they make, e.g. the nine flush outs of a flush draw
+    pub outs: BTreeMap<HandRanking, Vec<Card>>,
+    // The chance of ending up with each better ranking by the river
+    pub by_river: BTreeMap<HandRanking, f64>,
+}
+
+impl Outs {
+    pub fn count(&self) -> usize {
+        self.outs.values().map(|cards| cards.len()).sum()
+    }
+
+    // The chance of improving to any better ranking by the river
+    pub fn improve_probability(&self) -> f64 {
+        self.by_river.values().sum()
+    }
+
+    // The chance of ending up with the ranking or better by the river
+    pub fn probability_at_least(&self, ranking: HandRanking) -> f64 {
+        self.by_river.range(ranking..).map(|(_, p)| p).sum()
+    }
+}
+
+pub fn analyze(hole_cards: &[Card], board: &[Card]) -> Result<Outs, HandError> {
+    validate_cards(hole_cards, 2, 2)?;
+    validate_cards(board, 3, 4)?;
+
+    let known_cards: Vec<Card> = hole_cards.iter().chain(board).cloned().collect();
+    validate_cards(&known_cards, 5, 6)?;
+
+    let mut deck = Deck::new();
+    deck.remove(&known_cards)
+        .expect("known cards are validated to be unique");
+    let unseen = deck.cards();
+
+    let current = lookup::evaluate_best(&known_cards)?.ranking();
+
+    let mut cards = known_cards.clone();
+    let mut outs: BTreeMap<HandRanking, Vec<Card>> = BTreeMap::new();
+    for &card in unseen {
+        cards.push(card);
+        let ranking = lookup::evaluate_best(&cards)?.ranking();
+        cards.pop();
+
+        if ranking > current {
+            outs.entry(ranking).or_default().push(card);
+        }
+    }
+
+    // Every turn and river when there are two cards to come, every river otherwise
+    let runouts = combinations(unseen, 5 - board.len());
+    let mut by_river: BTreeMap<HandRanking, f64> = BTreeMap::new();
+    for runout in &runouts {
+        cards.extend(runout);
+        let ranking = lookup::evaluate_best(&cards)?.ranking();
+        cards.truncate(known_cards.len());
+
+        if ranking > current {
+            *by_river.entry(ranking).or_default() += 1.0 / runouts.len() as f64;
+        }
+    }
+
+    Ok(Outs {
+        current,
+        outs,
+        by_river,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use super::*;
+
+    fn cards(cards: &[&str]) -> Vec<Card> {
+        cards.iter().map(|c| Card::from_str(c).unwrap()).collect()
+    }
+
+    #[test]
+    fn test_flush_draw_on_the_flop() {
+        let outs = analyze(&cards(&["ah", "kh"]), &cards(&["2h", "7h", "tk"])).unwrap();
+
+        assert_eq!(outs.current, HandRanking::HighCard);
+        assert_eq!(outs.outs[&HandRanking::Flush].len(), 9);
+        // The flush comes on 378 out of the 1081 turns and rivers
+        assert!((outs.probability_at_least(HandRanking::Flush) - 378.0 / 1081.0).abs() < 1e-9)
+    }
+
+    #[test]
+    fn test_runner_runner_straight() {
+        // 6-7 with an eight needs two more cards for a straight: 9-T, 5-9 or 4-5
+        let outs = analyze(&cards(&["6h", "7s"]), &cards(&["8r", "kk", "2s"])).unwrap();
+
+        assert!(!outs.outs.contains_key(&HandRanking::Straight));
+        assert!((outs.by_river[&HandRanking::Straight] - 48.0 / 1081.0).abs() < 1e-9)
+    }
+
+    #[test]
+    fn test_turn_outs_are_river_chances() {
+        let outs = analyze(&cards(&["9h", "8h"]), &cards(&["th", "js", "2h", "3r"])).unwrap();
+
+        // The seven and queen of hearts make a flush, which is better than the straight
+        assert_eq!(outs.outs[&HandRanking::Flush].len(), 9);
+        assert_eq!(outs.outs[&HandRanking::Straight].len(), 6);
+        assert!((outs.improve_probability() - outs.count() as f64 / 46.0).abs() < 1e-9)
+    }
+
+    #[test]
+    fn test_outs_errors() {
+        let hole_cards = cards(&["ah", "kh"]);
+
+        assert_eq!(
+            analyze(&hole_cards, &cards(&["2h", "7h"])).unwrap_err(),
+            HandError::NotEnoughCards
+        );
+        assert_eq!(
+            analyze(&hole_cards, &cards(&["2h", "7h", "tk", "js", "qs"])).unwrap_err(),
+            HandError::TooManyCards
+        );
+        assert_eq!(
+            analyze(&hole_cards, &cards(&["ah", "7h", "tk"])).unwrap_err(),
+            HandError::DuplicateCards
+        )
+    }
+}
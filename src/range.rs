@@ -0,0 +1,406 @@
+use std::{fmt, str::FromStr};
+
+use crate::card::{card::Card, cardvalue::CardValue, suit::Suit};
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum RangeError {
+    EmptyRange,
+    InvalidHand(String),
+    InvalidSpan(String),
+    InvalidWeight(String),
+}
+
+impl fmt::Display for RangeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RangeError::EmptyRange => write!(f, "Range has no hands"),
+            RangeError::InvalidHand(hand) => write!(f, "Invalid hand in range: {}", hand),
+            RangeError::InvalidSpan(span) => write!(f, "Invalid span in range: {}", span),
+            RangeError::InvalidWeight(weight) => {
+                write!(
+                    f,
+                    "Invalid weight in range: {}, must be above 0 and at most 1",
+                    weight
+                )
+            }
+        }
+    }
+}
+
+// One two card hand in a range, with the higher card first. The weight is the share of the time
+// the hand is played, so "AKs:0.5" means each suited ace king is in the range half of the time.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct Combo {
+    pub cards: [Card; 2],
+    pub weight: f64,
+}
+
+// A range of hold'em hands written like "QQ+, AKs, A5s-A2s, KQo, 76s+", where
+//
+// - "AK" is every ace king, "AKs" only the suited and "AKo" only the offsuit ones
+// - "QQ+" is queens or better, "K9s+" is K9s up to KQs, and for connectors like "76s+" both
+//   cards go up together: 76s, 87s and so on up to AKs
+// - "A5s-A2s" is every hand between the two, "QQ-99" works the same for pairs
+// - "AhKs" is that exact hand
+// - ":0.5" after a hand gives it a weight
+//
+// Card values are case insensitive and the suits of exact hands use the same letters as `Card`.
+// When a hand is given more than once, the last weight is used.
+#[derive(Debug, PartialEq, Clone, Default)]
+pub struct Range {
+    pub combos: Vec<Combo>,
+}
+
+impl Range {
+    pub fn len(&self) -> usize {
+        self.combos.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.combos.is_empty()
+    }
+
+    pub fn contains(&self, cards: [Card; 2]) -> bool {
+        let cards = ordered(cards);
+        self.combos.iter().any(|combo| combo.cards == cards)
+    }
+
+    fn add(&mut self, cards: [Card; 2], weight: f64) {
+        let cards = ordered(cards);
+
+        match self.combos.iter_mut().find(|combo| combo.cards == cards) {
+            Some(combo) => combo.weight = weight,
+            None => self.combos.push(Combo { cards, weight }),
+        }
+    }
+}
+
+impl FromStr for Range {
+    type Err = RangeError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut range = Range::default();
+
+        for token in s
+            .split(|c: char| c == ',' || c.is_whitespace())
+            .filter(|token| !token.is_empty())
+        {
+            let (hand, weight) = match token.split_once(':') {
+                Some((hand, weight)) => (hand, parse_weight(weight)?),
+                None => (token, 1.0),
+            };
+
+            for cards in parse_hands(hand)? {
+                range.add(cards, weight);
+            }
+        }
+
+        if range.is_empty() {
+            return Err(RangeError::EmptyRange);
+        }
+
+        Ok(range)
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+enum Suitedness {
+    Suited,
+    Offsuit,
+    Any,
+}
+
+// A hand without suits, like AKs or QQ
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+struct HandClass {
+    high: CardValue,
+    low: CardValue,
+    suitedness: Suitedness,
+}
+
+impl HandClass {
+    fn gap(&self) -> u8 {
+        self.high as u8 - self.low as u8
+    }
+
+    // The same kind of hand with both cards moved up by `steps`
+    fn shifted(&self, steps: u8) -> HandClass {
+        HandClass {
+            high: value(self.high as u8 + steps),
+            low: value(self.low as u8 + steps),
+            suitedness: self.suitedness,
+        }
+    }
+
+    fn with_low(&self, low: u8) -> HandClass {
+        HandClass {
+            low: value(low),
+            ..*self
+        }
+    }
+
+    fn combos(&self) -> Vec<[Card; 2]> {
+        let mut combos = Vec::new();
+
+        for (i, &first) in Suit::ALL.iter().enumerate() {
+            for (j, &second) in Suit::ALL.iter().enumerate() {
+                let included = match self.suitedness {
+                    _ if self.high == self.low => i < j,
+                    Suitedness::Suited => i == j,
+                    Suitedness::Offsuit => i != j,
+                    Suitedness::Any => true,
+                };
+
+                if included {
+                    combos.push([
+                        Card {
+                            value: self.high,
+                            suit: first,
+                        },
+                        Card {
+                            value: self.low,
+                            suit: second,
+                        },
+                    ]);
+                }
+            }
+        }
+
+        combos
+    }
+}
+
+fn parse_hands(hand: &str) -> Result<Vec<[Card; 2]>, RangeError> {
+    let text = hand.to_lowercase();
+
+    let classes = if let Some((from, to)) = text.split_once('-') {
+        parse_span(parse_class(from, hand)?, parse_class(to, hand)?, hand)?
+    } else if let Some(class) = text.strip_suffix('+') {
+        parse_plus(parse_class(class, hand)?)
+    } else if text.len() == 4 {
+        return parse_exact(&text, hand).map(|cards| vec![cards]);
+    } else {
+        vec![parse_class(&text, hand)?]
+    };
+
+    Ok(classes.iter().flat_map(|class| class.combos()).collect())
+}
+
+fn parse_class(text: &str, hand: &str) -> Result<HandClass, RangeError> {
+    let invalid = || RangeError::InvalidHand(hand.to_string());
+
+    let chars: Vec<char> = text.chars().collect();
+    if chars.len() != 2 && chars.len() != 3 {
+        return Err(invalid());
+    }
+
+    let first = CardValue::from_str(&chars[0].to_string()).map_err(|_| invalid())?;
+    let second = CardValue::from_str(&chars[1].to_string()).map_err(|_| invalid())?;
+
+    let suitedness = match chars.get(2) {
+        None => Suitedness::Any,
+        Some('s') => Suitedness::Suited,
+        Some('o') => Suitedness::Offsuit,
+        Some(_) => return Err(invalid()),
+    };
+
+    // A pair can't be suited, and a pair is always offsuit so "QQo" says nothing more than "QQ"
+    if first == second && suitedness != Suitedness::Any {
+        return Err(invalid());
+    }
+
+    Ok(HandClass {
+        high: first.max(second),
+        low: first.min(second),
+        suitedness,
+    })
+}
+
+// Pairs and connectors go up with both cards, other hands only with the lower card
+fn parse_plus(class: HandClass) -> Vec<HandClass> {
+    if class.gap() <= 1 {
+        (0..=CardValue::A as u8 - class.high as u8)
+            .map(|steps| class.shifted(steps))
+            .collect()
+    } else {
+        (class.low as u8..class.high as u8)
+            .map(|low| class.with_low(low))
+            .collect()
+    }
+}
+
+// A span either keeps the higher card and goes through the lower cards, like A5s-A2s, or keeps
+// the gap between the cards and moves both, like QQ-99 or T9s-54s
+fn parse_span(from: HandClass, to: HandClass, hand: &str) -> Result<Vec<HandClass>, RangeError> {
+    let (top, bottom) = if (from.high, from.low) >= (to.high, to.low) {
+        (from, to)
+    } else {
+        (to, from)
+    };
+
+    if top.suitedness != bottom.suitedness {
+        return Err(RangeError::InvalidSpan(hand.to_string()));
+    }
+
+    if top.high == bottom.high && top.high != top.low && top.high != bottom.low {
+        Ok((bottom.low as u8..=top.low as u8)
+            .map(|low| top.with_low(low))
+            .collect())
+    } else if top.gap() == bottom.gap() {
+        Ok((0..=top.high as u8 - bottom.high as u8)
+            .map(|steps| bottom.shifted(steps))
+            .collect())
+    } else {
+        Err(RangeError::InvalidSpan(hand.to_string()))
+    }
+}
+
+fn parse_exact(text: &str, hand: &str) -> Result<[Card; 2], RangeError> {
+    let invalid = || RangeError::InvalidHand(hand.to_string());
+
+    let first = Card::from_str(text.get(..2).ok_or_else(invalid)?).map_err(|_| invalid())?;
+    let second = Card::from_str(text.get(2..).ok_or_else(invalid)?).map_err(|_| invalid())?;
+
+    if first == second || first.is_joker() || second.is_joker() {
+        return Err(invalid());
+    }
+
+    Ok([first, second])
+}
+
+fn parse_weight(weight: &str) -> Result<f64, RangeError> {
+    match weight.parse::<f64>() {
+        Ok(w) if w > 0.0 && w <= 1.0 => Ok(w),
+        _ => Err(RangeError::InvalidWeight(weight.to_string())),
+    }
+}
+
+fn value(value: u8) -> CardValue {
+    CardValue::ALL[(value - CardValue::Two as u8) as usize]
+}
+
+// The higher card first, and for a pair the suit that comes first in `Suit::ALL`, like the
+// combos of a `HandClass`
+fn ordered([first, second]: [Card; 2]) -> [Card; 2] {
+    let suit_index = |card: &Card| Suit::ALL.iter().position(|&suit| suit == card.suit);
+
+    if first.value > second.value
+        || (first.value == second.value && suit_index(&first) <= suit_index(&second))
+    {
+        [first, second]
+    } else {
+        [second, first]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn range(text: &str) -> Range {
+        Range::from_str(text).unwrap()
+    }
+
+    fn card(card: &str) -> Card {
+        Card::from_str(card).unwrap()
+    }
+
+    #[test]
+    fn test_range_sizes() {
+        let ranges = [
+            ("AA", 6),
+            ("AKs", 4),
+            ("AKo", 12),
+            ("AK", 16),
+            ("QQ+", 18),
+            ("A5s-A2s", 16),
+            ("QQ-99", 24),
+            ("K9s+", 16),
+            ("76s+", 32),
+            ("22+", 78),
+            // AKs is also part of 76s+ and only counted once
+            ("QQ+, AKs, A5s-A2s, KQo, 76s+", 18 + 16 + 12 + 32),
+        ];
+
+        for (text, size) in ranges {
+            assert_eq!(range(text).len(), size, "{}", text)
+        }
+    }
+
+    #[test]
+    fn test_connectors_go_up_together() {
+        let connectors = range("76s+");
+
+        assert!(connectors.contains([card("8h"), card("7h")]));
+        assert!(connectors.contains([card("ak"), card("kk")]));
+        assert!(!connectors.contains([card("8h"), card("6h")]))
+    }
+
+    #[test]
+    fn test_kicker_goes_up() {
+        let kings = range("K9s+");
+
+        assert!(kings.contains([card("ks"), card("qs")]));
+        assert!(kings.contains([card("9s"), card("ks")]));
+        assert!(!kings.contains([card("as"), card("ks")]));
+        assert!(!kings.contains([card("ks"), card("8s")]))
+    }
+
+    #[test]
+    fn test_exact_hand() {
+        let exact = range("AhKs");
+
+        assert_eq!(exact.len(), 1);
+        assert_eq!(exact.combos[0].cards, [card("ah"), card("ks")]);
+        assert_eq!(range("ksah"), exact)
+    }
+
+    #[test]
+    fn test_pairs_in_any_suit_order() {
+        let aces = range("AA");
+
+        assert!(aces.contains([card("as"), card("ah")]));
+        assert!(aces.contains([card("ah"), card("as")]));
+        assert_eq!(range("AA, AsAh"), aces);
+        assert_eq!(range("AsAh, AhAs").len(), 1)
+    }
+
+    #[test]
+    fn test_weights() {
+        let weighted = range("AKs:0.5, AKo");
+
+        assert_eq!(weighted.len(), 16);
+        assert!(weighted.combos[..4].iter().all(|combo| combo.weight == 0.5));
+        assert!(weighted.combos[4..].iter().all(|combo| combo.weight == 1.0));
+        assert_eq!(range("AK, AKs:0.25").combos[0].weight, 0.25)
+    }
+
+    #[test]
+    fn test_span_order_does_not_matter() {
+        assert_eq!(range("A2s-A5s").len(), 16);
+        assert_eq!(range("99-QQ").len(), 24);
+        assert_eq!(range("54s-T9s").len(), 24)
+    }
+
+    #[test]
+    fn test_range_errors() {
+        let errors = [
+            ("", RangeError::EmptyRange),
+            (" , ", RangeError::EmptyRange),
+            ("AAs", RangeError::InvalidHand("AAs".to_string())),
+            ("AKx", RangeError::InvalidHand("AKx".to_string())),
+            ("A1s", RangeError::InvalidHand("A1s".to_string())),
+            ("AhAh", RangeError::InvalidHand("AhAh".to_string())),
+            ("AKQJs", RangeError::InvalidHand("AKQJs".to_string())),
+            ("A5s-K2s", RangeError::InvalidSpan("A5s-K2s".to_string())),
+            ("A5s-A2o", RangeError::InvalidSpan("A5s-A2o".to_string())),
+            ("AKs:2", RangeError::InvalidWeight("2".to_string())),
+            ("AKs:0", RangeError::InvalidWeight("0".to_string())),
+            ("AKs:half", RangeError::InvalidWeight("half".to_string())),
+        ];
+
+        for (text, error) in errors {
+            assert_eq!(Range::from_str(text).unwrap_err(), error, "{}", text)
+        }
+    }
+}
@@ -0,0 +1,150 @@
+use crate::{
+    card::card::Card,
+    eval::{validate_cards, BestHand, Eval, HandError},
+    hand_ranks::Rules,
+};
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Showdown {
+    // The best hand of each player, in the same order as the players were given
+    pub hands: Vec<BestHand>,
+    // Player indexes grouped by the strength of their hand, best hand first.
+    // Players in the same group have equal hands and split the pot.
+    pub standings: Vec<Vec<usize>>,
+}
+
+impl Showdown {
+    pub fn winners(&self) -> &[usize] {
+        self.standings
+            .first()
+            .map_or(&[], |winners| winners.as_slice())
+    }
+}
+
+// Evaluates the hole cards of every player together with the shared board and ranks the
+// players against each other, using the kickers to break ties between equal rankings.
+pub fn showdown(board: &[Card], players: &[Vec<Card>]) -> Result<Showdown, HandError> {
+    showdown_with_rules(board, players, Rules::Standard)
+}
+
+pub fn showdown_with_rules(
+    board: &[Card],
+    players: &[Vec<Card>],
+    rules: Rules,
+) -> Result<Showdown, HandError> {
+    let all_cards: Vec<Card> = board
+        .iter()
+        .chain(players.iter().flatten())
+        .cloned()
+        .collect();
+    validate_cards(&all_cards, 0, usize::MAX)?;
+
+    let hands = players
+        .iter()
+        .map(|hole_cards| {
+            let cards: Vec<Card> = hole_cards.iter().chain(board).cloned().collect();
+            Eval::with_rules(cards, rules).evaluate_best()
+        })
+        .collect::<Result<Vec<BestHand>, HandError>>()?;
+
+    let mut order: Vec<usize> = (0..hands.len()).collect();
+    order.sort_by(|&a, &b| hands[b].value.cmp(&hands[a].value));
+
+    let mut standings: Vec<Vec<usize>> = Vec::new();
+    for player in order {
+        match standings.last_mut() {
+            Some(group) if hands[group[0]].value == hands[player].value => group.push(player),
+            _ => standings.push(vec![player]),
+        }
+    }
+
+    Ok(Showdown { hands, standings })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use crate::hand_ranks::HandRanking;
+
+    use super::*;
+
+    fn cards(cards: &[&str]) -> Vec<Card> {
+        cards.iter().map(|c| Card::from_str(c).unwrap()).collect()
+    }
+
+    #[test]
+    fn test_showdown_single_winner() {
+        let board = cards(&["kr", "7s", "2h", "9r", "jk"]);
+        let players = vec![
+            cards(&["ks", "qh"]),
+            cards(&["7h", "7k"]),
+            cards(&["ah", "3h"]),
+        ];
+
+        let showdown = showdown(&board, &players).unwrap();
+
+        assert_eq!(showdown.winners(), &[1]);
+        assert_eq!(showdown.standings, vec![vec![1], vec![0], vec![2]]);
+        assert_eq!(showdown.hands[1].ranking(), HandRanking::ThreeOfAKind)
+    }
+
+    #[test]
+    fn test_showdown_kicker_decides() {
+        let board = cards(&["kr", "7s", "2h", "9r", "4k"]);
+        let players = vec![cards(&["ks", "th"]), cards(&["kh", "qh"])];
+
+        let showdown = showdown(&board, &players).unwrap();
+
+        assert_eq!(showdown.winners(), &[1]);
+        assert_eq!(showdown.standings, vec![vec![1], vec![0]])
+    }
+
+    #[test]
+    fn test_showdown_split_pot() {
+        let board = cards(&["th", "js", "qr", "kk", "2h"]);
+        let players = vec![
+            cards(&["as", "3h"]),
+            cards(&["3r", "4r"]),
+            cards(&["ar", "5k"]),
+        ];
+
+        let showdown = showdown(&board, &players).unwrap();
+
+        assert_eq!(showdown.standings, vec![vec![0, 2], vec![1]]);
+        assert_eq!(showdown.winners(), &[0, 2])
+    }
+
+    #[test]
+    fn test_showdown_board_plays() {
+        let board = cards(&["ah", "ar", "as", "ak", "kh"]);
+        let players = vec![cards(&["2s", "3h"]), cards(&["4r", "5r"])];
+
+        let showdown = showdown(&board, &players).unwrap();
+
+        assert_eq!(showdown.winners(), &[0, 1])
+    }
+
+    #[test]
+    fn test_showdown_short_deck() {
+        let board = cards(&["as", "ar", "7s", "9s", "ks"]);
+        let players = vec![cards(&["ak", "kh"]), cards(&["ts", "6r"])];
+
+        let standard = showdown(&board, &players).unwrap();
+        let short_deck = showdown_with_rules(&board, &players, Rules::ShortDeck).unwrap();
+
+        assert_eq!(standard.winners(), &[0]);
+        assert_eq!(short_deck.winners(), &[1]);
+        assert_eq!(short_deck.hands[1].ranking(), HandRanking::Flush)
+    }
+
+    #[test]
+    fn test_showdown_duplicate_cards() {
+        let board = cards(&["kr", "7s", "2h", "9r", "4k"]);
+        let players = vec![cards(&["ks", "th"]), cards(&["ks", "qh"])];
+
+        let error = showdown(&board, &players).unwrap_err();
+
+        assert_eq!(error, HandError::DuplicateCards)
+    }
+}
@@ -0,0 +1,239 @@
+use std::{collections::HashMap, fmt::Display};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    card::{card::Card, cardvalue::CardValue},
+    eval::{validate_cards, HandError},
+};
+
+// In three card poker a straight is harder to get than a flush, so it ranks above it
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Hash)]
+pub enum ThreeCardRanking {
+    HighCard,
+    Pair,
+    Flush,
+    Straight,
+    ThreeOfAKind,
+    StraightFlush,
+}
+
+impl Display for ThreeCardRanking {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ThreeCardRanking::HighCard => write!(f, "High card"),
+            ThreeCardRanking::Pair => write!(f, "Pair"),
+            ThreeCardRanking::Flush => write!(f, "Flush"),
+            ThreeCardRanking::Straight => write!(f, "Straight"),
+            ThreeCardRanking::ThreeOfAKind => write!(f, "Three of a kind"),
+            ThreeCardRanking::StraightFlush => write!(f, "Straight flush"),
+        }
+    }
+}
+
+impl ThreeCardRanking {
+    // The pair plus bet is paid on the player's hand alone, at these odds to one.
+    // `None` means the bet is lost.
+    pub fn pair_plus(&self) -> Option<u64> {
+        match self {
+            ThreeCardRanking::StraightFlush => Some(40),
+            ThreeCardRanking::ThreeOfAKind => Some(30),
+            ThreeCardRanking::Straight => Some(6),
+            ThreeCardRanking::Flush => Some(3),
+            ThreeCardRanking::Pair => Some(1),
+            ThreeCardRanking::HighCard => None,
+        }
+    }
+
+    // The ante bonus is paid on the ante for a straight or better, at these odds to one,
+    // whatever the dealer has
+    pub fn ante_bonus(&self) -> Option<u64> {
+        match self {
+            ThreeCardRanking::StraightFlush => Some(5),
+            ThreeCardRanking::ThreeOfAKind => Some(4),
+            ThreeCardRanking::Straight => Some(1),
+            _ => None,
+        }
+    }
+}
+
+// Like `HandValue`, the ranking is compared first and then the card values that break ties
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Hash)]
+pub struct ThreeCardValue {
+    pub ranking: ThreeCardRanking,
+    pub ranks: Vec<CardValue>,
+}
+
+impl ThreeCardValue {
+    // The dealer needs queen high or better to play
+    pub fn dealer_qualifies(&self) -> bool {
+        self.ranking > ThreeCardRanking::HighCard || self.ranks[0] >= CardValue::Q
+    }
+}
+
+pub fn evaluate(cards: &[Card]) -> Result<ThreeCardValue, HandError> {
+    validate_cards(cards, 3, 3)?;
+
+    let mut rank_count: HashMap<CardValue, usize> = HashMap::new();
+    for card in cards {
+        *rank_count.entry(card.value).or_insert(0) += 1;
+    }
+
+    let mut groups: Vec<(usize, CardValue)> = rank_count
+        .into_iter()
+        .map(|(value, count)| (count, value))
+        .collect();
+    groups.sort_by(|a, b| b.cmp(a));
+    let mut ranks: Vec<CardValue> = groups.iter().map(|&(_, value)| value).collect();
+
+    let is_flush = cards.iter().all(|card| card.suit == cards[0].suit);
+    let is_wheel = ranks == [CardValue::A, CardValue::Three, CardValue::Two];
+    let is_straight = ranks.len() == 3 && (ranks[0] as usize == ranks[2] as usize + 2 || is_wheel);
+
+    // A-2-3 is the lowest straight, so the ace counts as one
+    if is_wheel {
+        ranks.rotate_left(1);
+    }
+
+    let ranking = match (groups[0].0, is_straight, is_flush) {
+        (3, _, _) => ThreeCardRanking::ThreeOfAKind,
+        (2, _, _) => ThreeCardRanking::Pair,
+        (_, true, true) => ThreeCardRanking::StraightFlush,
+        (_, true, false) => ThreeCardRanking::Straight,
+        (_, false, true) => ThreeCardRanking::Flush,
+        _ => ThreeCardRanking::HighCard,
+    };
+
+    Ok(ThreeCardValue { ranking, ranks })
+}
+
+// Settles the ante and play bets of a player who plays their hand, where the play bet is the
+// same size as the ante. Returns what the player wins, or loses when negative.
+//
+// When the dealer doesn't qualify the ante is paid and the play bet is returned. Otherwise the
+// better hand wins both bets and a tie returns them. The ante bonus is paid either way.
+pub fn settle_ante_and_play(player: &ThreeCardValue, dealer: &ThreeCardValue, ante: u64) -> i64 {
+    let ante = ante as i64;
+    let bonus = player
+        .ranking
+        .ante_bonus()
+        .map_or(0, |odds| odds as i64 * ante);
+
+    let result = if !dealer.dealer_qualifies() {
+        ante
+    } else if player > dealer {
+        2 * ante
+    } else if player < dealer {
+        -2 * ante
+    } else {
+        0
+    };
+
+    result + bonus
+}
+
+// Settles the pair plus bet, which only depends on the player's hand.
+// Returns what the player wins, or loses when negative.
+pub fn settle_pair_plus(player: &ThreeCardValue, bet: u64) -> i64 {
+    match player.ranking.pair_plus() {
+        Some(odds) => (odds * bet) as i64,
+        None => -(bet as i64),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use super::*;
+
+    fn cards(cards: &[&str]) -> Vec<Card> {
+        cards.iter().map(|c| Card::from_str(c).unwrap()).collect()
+    }
+
+    #[test]
+    fn test_three_card_rankings() {
+        let hands = [
+            (["ah", "kh", "qh"], ThreeCardRanking::StraightFlush),
+            (["7h", "7s", "7r"], ThreeCardRanking::ThreeOfAKind),
+            (["ah", "2s", "3r"], ThreeCardRanking::Straight),
+            (["2h", "9h", "kh"], ThreeCardRanking::Flush),
+            (["9h", "9s", "kh"], ThreeCardRanking::Pair),
+            (["2h", "9s", "kh"], ThreeCardRanking::HighCard),
+            (["ks", "ah", "2r"], ThreeCardRanking::HighCard),
+        ];
+
+        for (hand, ranking) in hands {
+            assert_eq!(evaluate(&cards(&hand)).unwrap().ranking, ranking)
+        }
+    }
+
+    #[test]
+    fn test_straight_beats_flush() {
+        let straight = evaluate(&cards(&["4h", "5s", "6r"])).unwrap();
+        let flush = evaluate(&cards(&["ah", "kh", "9h"])).unwrap();
+
+        assert!(straight > flush)
+    }
+
+    #[test]
+    fn test_ace_low_straight_is_lowest() {
+        let wheel = evaluate(&cards(&["ah", "2s", "3r"])).unwrap();
+        let four_high = evaluate(&cards(&["2h", "3s", "4r"])).unwrap();
+        let ace_high = evaluate(&cards(&["ah", "ks", "qr"])).unwrap();
+
+        assert!(wheel < four_high);
+        assert!(four_high < ace_high)
+    }
+
+    #[test]
+    fn test_dealer_qualifies() {
+        let queen_high = evaluate(&cards(&["qh", "5s", "3r"])).unwrap();
+        let jack_high = evaluate(&cards(&["jh", "5s", "3r"])).unwrap();
+        let pair = evaluate(&cards(&["2h", "2s", "3r"])).unwrap();
+
+        assert!(queen_high.dealer_qualifies());
+        assert!(!jack_high.dealer_qualifies());
+        assert!(pair.dealer_qualifies())
+    }
+
+    #[test]
+    fn test_settle_ante_and_play() {
+        let straight = evaluate(&cards(&["4h", "5s", "6r"])).unwrap();
+        let pair = evaluate(&cards(&["9h", "9s", "kh"])).unwrap();
+        let jack_high = evaluate(&cards(&["jh", "5s", "3r"])).unwrap();
+        let same_pair = evaluate(&cards(&["9r", "9k", "kk"])).unwrap();
+
+        assert_eq!(settle_ante_and_play(&pair, &jack_high, 10), 10);
+        assert_eq!(settle_ante_and_play(&straight, &pair, 10), 30);
+        assert_eq!(settle_ante_and_play(&pair, &straight, 10), -20);
+        assert_eq!(settle_ante_and_play(&pair, &same_pair, 10), 0)
+    }
+
+    #[test]
+    fn test_settle_pair_plus() {
+        let straight_flush = evaluate(&cards(&["4h", "5h", "6h"])).unwrap();
+        let pair = evaluate(&cards(&["9h", "9s", "kh"])).unwrap();
+        let high_card = evaluate(&cards(&["2h", "9s", "kh"])).unwrap();
+
+        assert_eq!(settle_pair_plus(&straight_flush, 5), 200);
+        assert_eq!(settle_pair_plus(&pair, 5), 5);
+        assert_eq!(settle_pair_plus(&high_card, 5), -5)
+    }
+
+    #[test]
+    fn test_three_card_errors() {
+        assert_eq!(
+            evaluate(&cards(&["4h", "5h"])).unwrap_err(),
+            HandError::NotEnoughCards
+        );
+        assert_eq!(
+            evaluate(&cards(&["4h", "5h", "6h", "7h"])).unwrap_err(),
+            HandError::TooManyCards
+        );
+        assert_eq!(
+            evaluate(&cards(&["4h", "5h", "4h"])).unwrap_err(),
+            HandError::DuplicateCards
+        )
+    }
+}
@@ -0,0 +1,211 @@
+use crate::{
+    card::{card::Card, cardvalue::CardValue, suit::Suit},
+    eval::{combinations, BestHand, Eval, HandError},
+    hand_ranks::{HandRanking, HandValue, Rules},
+};
+
+// Evaluates five cards where the joker and every card with one of the `wild_values`, like the
+// twos in deuces wild, can stand in for any card. Each wild card becomes the card that gives the
+// best hand, and a wild card may become a card that is already in the hand, which is how five of
+// a kind is made.
+pub fn evaluate(cards: &[Card], wild_values: &[CardValue]) -> Result<HandValue, HandError> {
+    validate(cards, 5, 5)?;
+
+    let is_wild = |card: &Card| card.is_joker() || wild_values.contains(&card.value);
+    let naturals: Vec<Card> = cards
+        .iter()
+        .filter(|card| !is_wild(card))
+        .cloned()
+        .collect();
+    let wild_cards = cards.len() - naturals.len();
+
+    if wild_cards == 0 {
+        return Eval::from(naturals).evaluate_value();
+    }
+
+    // When every natural card has the same value, the wild cards can make five of a kind,
+    // which beats every other hand. Five wild cards become five aces.
+    if naturals.windows(2).all(|c| c[0].value == c[1].value) {
+        let value = naturals.first().map_or(CardValue::A, |card| card.value);
+
+        return Ok(HandValue {
+            ranking: HandRanking::FiveOfAKind,
+            ranks: vec![value],
+            rules: Rules::Standard,
+        });
+    }
+
+    // The suit of a wild card only matters for flushes, so it is enough to try the suit the
+    // natural cards share, if they are all the same suit, and one suit that can't make a flush
+    let flush_suit = naturals
+        .windows(2)
+        .all(|c| c[0].suit == c[1].suit)
+        .then_some(naturals[0].suit);
+    let other_suit = Suit::ALL
+        .into_iter()
+        .find(|&suit| Some(suit) != flush_suit)
+        .expect("there are four suits");
+
+    let substitutes: Vec<Card> = CardValue::ALL
+        .iter()
+        .flat_map(|&value| {
+            flush_suit
+                .into_iter()
+                .chain([other_suit])
+                .map(move |suit| Card { value, suit })
+        })
+        .collect();
+
+    let mut best_value: Option<HandValue> = None;
+    for substitution in multisets(&substitutes, wild_cards) {
+        let hand: Vec<Card> = naturals.iter().chain(&substitution).cloned().collect();
+        let value = Eval::from(hand).value();
+
+        if best_value.as_ref().is_none_or(|best| value > *best) {
+            best_value = Some(value);
+        }
+    }
+
+    best_value.ok_or(HandError::NotEnoughCards)
+}
+
+// Finds the best five card hand out of five to seven cards with wild cards, the same way as
+// `Eval::evaluate_best`. The cards of the best hand are the cards as they were given, with
+// the wild cards still wild.
+pub fn evaluate_best(cards: &[Card], wild_values: &[CardValue]) -> Result<BestHand, HandError> {
+    validate(cards, 5, 7)?;
+
+    let mut best_hand: Option<BestHand> = None;
+    for hand in combinations(cards, 5) {
+        let value = evaluate(&hand, wild_values)?;
+
+        if best_hand.as_ref().is_none_or(|best| value > best.value) {
+            best_hand = Some(BestHand { value, cards: hand });
+        }
+    }
+
+    best_hand.ok_or(HandError::NotEnoughCards)
+}
+
+fn validate(cards: &[Card], min: usize, max: usize) -> Result<(), HandError> {
+    if cards.len() > max {
+        return Err(HandError::TooManyCards);
+    }
+
+    if cards.len() < min {
+        return Err(HandError::NotEnoughCards);
+    }
+
+    let mut unique = cards.to_vec();
+    unique.sort();
+    unique.dedup();
+
+    if unique.len() != cards.len() {
+        return Err(HandError::DuplicateCards);
+    }
+
+    Ok(())
+}
+
+// Every way of picking `k` cards when the same card can be picked more than once
+fn multisets(cards: &[Card], k: usize) -> Vec<Vec<Card>> {
+    if k == 0 {
+        return vec![Vec::new()];
+    }
+
+    let mut result = Vec::new();
+    for (i, &card) in cards.iter().enumerate() {
+        for mut rest in multisets(&cards[i..], k - 1) {
+            rest.insert(0, card);
+            result.push(rest);
+        }
+    }
+
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use super::*;
+
+    fn cards(cards: &[&str]) -> Vec<Card> {
+        cards.iter().map(|c| Card::from_str(c).unwrap()).collect()
+    }
+
+    #[test]
+    fn test_joker_makes_five_of_a_kind() {
+        let value = evaluate(&cards(&["jo", "ah", "as", "ar", "ak"]), &[]).unwrap();
+
+        assert_eq!(value.ranking, HandRanking::FiveOfAKind);
+        assert_eq!(value.ranks, vec![CardValue::A])
+    }
+
+    #[test]
+    fn test_joker_completes_royal_straight_flush() {
+        let value = evaluate(&cards(&["jo", "kh", "qh", "jh", "th"]), &[]).unwrap();
+
+        assert_eq!(value.ranking, HandRanking::RoyalStraightFlush)
+    }
+
+    #[test]
+    fn test_joker_makes_best_pair() {
+        let value = evaluate(&cards(&["jo", "2s", "5h", "9r", "kk"]), &[]).unwrap();
+
+        assert_eq!(value.ranking, HandRanking::Pair);
+        assert_eq!(value.ranks[0], CardValue::K)
+    }
+
+    #[test]
+    fn test_joker_makes_four_of_a_kind() {
+        let value = evaluate(&cards(&["jo", "ah", "as", "ar", "kk"]), &[]).unwrap();
+
+        assert_eq!(value.ranking, HandRanking::FourOfAKind);
+        assert_eq!(value.ranks, vec![CardValue::A, CardValue::K])
+    }
+
+    #[test]
+    fn test_deuces_wild() {
+        let straight_flush =
+            evaluate(&cards(&["2h", "2s", "7h", "8h", "9h"]), &[CardValue::Two]).unwrap();
+        let five_nines =
+            evaluate(&cards(&["2h", "2s", "2r", "2k", "9h"]), &[CardValue::Two]).unwrap();
+        let natural = evaluate(&cards(&["2h", "2s", "7h", "8h", "9h"]), &[]).unwrap();
+
+        assert_eq!(straight_flush.ranking, HandRanking::StraightFlush);
+        assert_eq!(straight_flush.ranks[0], CardValue::J);
+        assert_eq!(five_nines.ranking, HandRanking::FiveOfAKind);
+        assert_eq!(five_nines.ranks, vec![CardValue::Nine]);
+        assert_eq!(natural.ranking, HandRanking::Pair)
+    }
+
+    #[test]
+    fn test_five_of_a_kind_beats_royal_straight_flush() {
+        let five_twos = evaluate(&cards(&["jo", "2h", "2s", "2r", "2k"]), &[]).unwrap();
+        let royal = evaluate(&cards(&["jo", "kh", "qh", "jh", "th"]), &[]).unwrap();
+
+        assert!(five_twos > royal)
+    }
+
+    #[test]
+    fn test_wild_best_hand() {
+        let best_hand =
+            evaluate_best(&cards(&["jo", "kh", "kr", "7s", "7h", "2s", "3h"]), &[]).unwrap();
+
+        assert_eq!(best_hand.ranking(), HandRanking::FullHouse);
+        assert!(best_hand.cards.contains(&Card::JOKER))
+    }
+
+    #[test]
+    fn test_wild_errors() {
+        assert_eq!(
+            evaluate(&cards(&["jo", "kh", "qh", "jh"]), &[]).unwrap_err(),
+            HandError::NotEnoughCards
+        );
+        assert_eq!(
+            evaluate(&cards(&["jo", "jo", "qh", "jh", "th"]), &[]).unwrap_err(),
+            HandError::DuplicateCards
+        )
+    }
+}